@@ -1,20 +1,21 @@
 use amethyst::ecs::prelude::{Component, DenseVecStorage};
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use std::collections::HashMap;
 
-use super::HitboxProperties;
+use super::{generate_arena, Cooldown, Health, HitboxProperties, PickupKinds, ProceduralArenaProperties};
 
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Deserialize)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ArenaElementKinds {
     Open,
     Wall,
     Zone,
+    Teleporter(u32), //linked to the other teleporter with the same pair id
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Deserialize)]
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ArenaElement {
     pub kind: ArenaElementKinds,
     pub x: f32,
@@ -26,22 +27,245 @@ impl Component for ArenaElement {
     type Storage = DenseVecStorage<Self>;
 }
 
+// Scripted movement for an arena element, relative to where the arena places it
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ArenaElementMotion {
+    Linear {dx: f32, dy: f32, period: f32}, //out to the offset and back once per period, in seconds
+    Rotate {speed: f32}, //degrees per second, counterclockwise
+    Door {dx: f32, dy: f32, closed_time: f32, open_time: f32, slide_time: f32}, //slides open by the offset and back
+}
+
+impl ArenaElementMotion {
+    // Offset from the element's placement and extra rotation in degrees, at a time into the script
+    pub fn offset_at(&self, time: f32) -> (f32, f32, f32) {
+        match *self {
+            ArenaElementMotion::Linear {dx, dy, period} => {
+                if period <= 0.0 {
+                    return (0.0, 0.0, 0.0);
+                }
+                let phase = (time % period) / period;
+                let extent = if phase < 0.5 { phase * 2.0 } else { 2.0 - phase * 2.0 };
+                (dx * extent, dy * extent, 0.0)
+            },
+            ArenaElementMotion::Rotate {speed} => (0.0, 0.0, (speed * time) % 360.0),
+            ArenaElementMotion::Door {dx, dy, closed_time, open_time, slide_time} => {
+                let cycle = closed_time + open_time + slide_time * 2.0;
+                if cycle <= 0.0 {
+                    return (0.0, 0.0, 0.0);
+                }
+                let time = time % cycle;
+                let slide_time = slide_time.max(f32::EPSILON);
+
+                let extent = if time < closed_time {
+                    0.0
+                } else if time < closed_time + slide_time {
+                    (time - closed_time) / slide_time
+                } else if time < closed_time + slide_time + open_time {
+                    1.0
+                } else {
+                    1.0 - (time - closed_time - slide_time - open_time) / slide_time
+                };
+                (dx * extent, dy * extent, 0.0)
+            },
+        }
+    }
+}
+
 // The properties of ArenaElementHitbox should never be accessed after arena initialization from .ron file,
 // instead the components formed from these properties should be accessed
-#[derive(Copy, Clone, Debug, PartialEq, Deserialize)]
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ArenaElementHitbox {
     pub element: ArenaElement,
     pub hitbox: HitboxProperties,
+    #[serde(default)]
+    pub motion: Option<ArenaElementMotion>,
+    #[serde(default)]
+    pub health: Option<f32>, //walls with hit points can be shot down
+}
+
+// An arena element following its motion script, moved each frame by the ArenaMotionSystem
+#[derive(Debug, PartialEq)]
+pub struct MovingElement {
+    pub motion: ArenaElementMotion,
+    origin_x: f32,
+    origin_y: f32,
+    origin_rotation: f32, //degrees
+    elapsed: f32,
+    pub dx: f32, //velocity, for collisions
+    pub dy: f32,
+    pub spin: f32, //radians per second
+}
+
+impl Component for MovingElement {
+    type Storage = DenseVecStorage<Self>;
+}
+
+impl MovingElement {
+    pub fn new(motion: ArenaElementMotion, x: f32, y: f32, rotation: f32) -> MovingElement {
+        MovingElement {
+            motion,
+            origin_x: x,
+            origin_y: y,
+            origin_rotation: rotation,
+            elapsed: 0.0,
+            dx: 0.0,
+            dy: 0.0,
+            spin: 0.0,
+        }
+    }
+
+    pub fn get_origin_rotation(&self) -> f32 {
+        self.origin_rotation
+    }
+
+    // Steps the script forward, returns the new position and rotation in degrees.
+    // A step of zero holds the element still.
+    pub fn advance(&mut self, dt: f32) -> (f32, f32, f32) {
+        let (old_x, old_y, old_rotation) = self.motion.offset_at(self.elapsed);
+        self.elapsed += dt;
+        let (x, y, rotation) = self.motion.offset_at(self.elapsed);
+
+        if dt > 0.0 {
+            self.dx = (x - old_x) / dt;
+            self.dy = (y - old_y) / dt;
+            self.spin = match self.motion {
+                ArenaElementMotion::Rotate {speed} => speed.to_radians(),
+                _ => (rotation - old_rotation).to_radians() / dt,
+            };
+        } else {
+            self.dx = 0.0;
+            self.dy = 0.0;
+            self.spin = 0.0;
+        }
+
+        (self.origin_x + x, self.origin_y + y, self.origin_rotation + rotation)
+    }
+}
+
+pub const WALL_DAMAGE_STATES: usize = 3; //intact, damaged, wrecked
+
+// A wall with hit points, shot down to rubble that ships fly over
+#[derive(Debug, PartialEq)]
+pub struct DestructibleWall {
+    pub index: usize, //place in the arena's elements, to keep it down in later rounds
+    pub health: Health,
+}
+
+impl Component for DestructibleWall {
+    type Storage = DenseVecStorage<Self>;
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Deserialize)]
+impl DestructibleWall {
+    pub fn new(index: usize, max_health: f32) -> DestructibleWall {
+        DestructibleWall {
+            index,
+            health: Health::new(max_health),
+        }
+    }
+
+    // Steps through the states evenly as health drops, rubble stays wrecked
+    pub fn damage_state(&self) -> usize {
+        if !self.health.is_alive() {
+            return WALL_DAMAGE_STATES - 1;
+        }
+
+        let lost = 1.0 - self.health.value / self.health.max;
+        ((lost * WALL_DAMAGE_STATES as f32) as usize).min(WALL_DAMAGE_STATES - 1)
+    }
+}
+
+// A chip knocked off a destructible wall, flies off spinning until its time is up
+#[derive(Debug, PartialEq)]
+pub struct Debris {
+    pub dx: f32,
+    pub dy: f32,
+    pub spin: f32, //radians per second
+    pub lifetime: f32, //seconds left
+}
+
+impl Component for Debris {
+    type Storage = DenseVecStorage<Self>;
+}
+
+pub const TELEPORTER_COOLDOWN: f32 = 0.5; //seconds a pad pair rests after sending something through
+
+// A teleporter pad, ships and shots entering it come out of its partner with the same speed,
+// their heading turned by the difference between the pads' rotations
+#[derive(Debug, PartialEq)]
+pub struct Teleporter {
+    pub pair_id: u32,
+    pub rotation: f32, //degrees
+    pub cooldown: Cooldown,
+    pub arrivals: Vec<u32>, //entity ids sent here, not sent back until they leave the pad
+}
+
+impl Component for Teleporter {
+    type Storage = DenseVecStorage<Self>;
+}
+
+impl Teleporter {
+    pub fn new(pair_id: u32, rotation: f32) -> Teleporter {
+        Teleporter {
+            pair_id,
+            rotation,
+            cooldown: Cooldown::new(0.0, TELEPORTER_COOLDOWN),
+            arrivals: vec![],
+        }
+    }
+
+    // Turn in radians from entering this pad to leaving the exit, and the velocity turned with it
+    pub fn turn_to(&self, exit: &Teleporter, dx: f32, dy: f32) -> (f32, f32, f32) {
+        let turn = (exit.rotation - self.rotation).to_radians();
+        let (sin, cos) = turn.sin_cos();
+
+        (turn, dx * cos - dy * sin, dx * sin + dy * cos)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct PlayerSpawnPoint {
     pub x: f32,
     pub y: f32,
     pub rotation: f32, //degrees, only supports multiples of 90deg angles due to rectangular hitbox logic
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Deserialize)]
+// Team play spawns, the team's players are spread evenly along the zone's longer side
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TeamSpawnZone {
+    pub team: usize,
+    pub x: f32, //center
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub rotation: f32, //degrees, the direction the team's players face
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PickupSpawnPoint {
+    pub x: f32,
+    pub y: f32,
+    pub kinds: Vec<PickupKinds>, //cycled through on each respawn
+    pub respawn_time: f32, //seconds
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FlagSpawnPoint {
+    pub team: usize,
+    pub x: f32, //the team's base, where its flag starts and captures are made
+    pub y: f32,
+}
+
+// Race checkpoints are listed in course order, the first is the start/finish line
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ArenaCheckpoint {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub rotation: i16, //degrees
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ArenaFloor {
     pub x: f32,
     pub y: f32,
@@ -49,13 +273,66 @@ pub struct ArenaFloor {
     pub height: f32,
 }
 
-#[derive(Clone, Debug, PartialEq, Deserialize, Default)]
+impl Component for ArenaFloor {
+    type Storage = DenseVecStorage<Self>;
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
 pub struct Arena {
     pub width: f32,
     pub height: f32,
     pub floor: Vec<ArenaFloor>,
     pub player_spawn_points: Vec<PlayerSpawnPoint>,
     pub arena_elements: Vec<ArenaElementHitbox>,
+    #[serde(default)]
+    pub pickup_spawns: Vec<PickupSpawnPoint>,
+    #[serde(default)]
+    pub flags: Vec<FlagSpawnPoint>,
+    #[serde(default)]
+    pub checkpoints: Vec<ArenaCheckpoint>,
+    #[serde(default)]
+    pub team_spawns: Vec<TeamSpawnZone>,
+}
+
+impl Arena {
+    // Spawn points by player id, one per player spawn point.
+    // With team play, players on a team with a spawn zone start in it instead.
+    pub fn get_spawn_points(&self, player_teams: Option<&[usize]>) -> Vec<PlayerSpawnPoint> {
+        let player_teams = match player_teams {
+            Some(player_teams) => player_teams,
+            None => return self.player_spawn_points.clone(),
+        };
+
+        self.player_spawn_points.iter().enumerate().map(|(player_id, player_spawn_point)| {
+            let team = match player_teams.get(player_id) {
+                Some(team) => *team,
+                None => return *player_spawn_point,
+            };
+
+            match self.team_spawns.iter().find(|team_spawn| team_spawn.team == team) {
+                Some(team_spawn) => {
+                    let teammates: Vec<usize> = (0..self.player_spawn_points.len())
+                        .filter(|id| player_teams.get(*id) == Some(&team))
+                        .collect();
+                    let place = teammates.iter().position(|id| *id == player_id).unwrap_or(0);
+                    let offset = (place as f32 + 0.5) / teammates.len() as f32 - 0.5;
+
+                    let (x, y) = if team_spawn.width >= team_spawn.height {
+                        (team_spawn.x + offset * team_spawn.width, team_spawn.y)
+                    } else {
+                        (team_spawn.x, team_spawn.y + offset * team_spawn.height)
+                    };
+
+                    PlayerSpawnPoint {
+                        x,
+                        y,
+                        rotation: team_spawn.rotation,
+                    }
+                },
+                None => *player_spawn_point,
+            }
+        }).collect()
+    }
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Deserialize)]
@@ -66,8 +343,106 @@ pub enum ArenaNames {
     StandardRace,
     ChaosCombat,
     LargeCombat,
+    Procedural(u64), //generated from the seed, see ProceduralArenaProperties
 }
 
 pub struct ArenaStoreResource {
     pub properties: HashMap<ArenaNames, Arena>,
-}
\ No newline at end of file
+    pub procedural: ProceduralArenaProperties,
+}
+
+impl ArenaStoreResource {
+    pub fn contains(&self, arena_name: &ArenaNames) -> bool {
+        match arena_name {
+            ArenaNames::Procedural(_) => true,
+            _ => self.properties.contains_key(arena_name),
+        }
+    }
+
+    pub fn get_properties(&self, arena_name: &ArenaNames) -> Arena {
+        match arena_name {
+            ArenaNames::Procedural(seed) => generate_arena(*seed, &self.procedural),
+            _ => match self.properties.get(arena_name) {
+                Some(arena_props_get) => (*arena_props_get).clone(),
+                _ => Arena::default(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn linear_motion_goes_out_and_back() {
+        let motion = ArenaElementMotion::Linear {dx: 40.0, dy: 0.0, period: 4.0};
+
+        assert_eq!(motion.offset_at(0.0), (0.0, 0.0, 0.0));
+        assert_eq!(motion.offset_at(1.0), (20.0, 0.0, 0.0));
+        assert_eq!(motion.offset_at(2.0), (40.0, 0.0, 0.0));
+        assert_eq!(motion.offset_at(3.0), (20.0, 0.0, 0.0));
+        assert_eq!(motion.offset_at(5.0), (20.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn door_waits_then_slides_open_and_shut() {
+        let motion = ArenaElementMotion::Door {dx: 0.0, dy: 50.0, closed_time: 2.0, open_time: 1.0, slide_time: 0.5};
+
+        assert_eq!(motion.offset_at(1.0).1, 0.0);
+        assert_eq!(motion.offset_at(2.25).1, 25.0);
+        assert_eq!(motion.offset_at(3.0).1, 50.0);
+        assert_eq!(motion.offset_at(3.75).1, 25.0);
+        assert_eq!(motion.offset_at(4.0).1, 0.0); //next cycle
+    }
+
+    #[test]
+    fn moving_element_tracks_velocity() {
+        let mut moving = MovingElement::new(ArenaElementMotion::Linear {dx: 0.0, dy: 40.0, period: 4.0}, 100.0, 100.0, 90.0);
+
+        assert_eq!(moving.advance(0.5), (100.0, 110.0, 90.0));
+        assert_eq!((moving.dx, moving.dy), (0.0, 20.0));
+
+        moving.advance(1.5);
+        assert_eq!(moving.advance(1.0), (100.0, 120.0, 90.0));
+        assert_eq!((moving.dx, moving.dy), (0.0, -20.0)); //turned around at the far end
+        assert_eq!(moving.spin, 0.0);
+
+        moving.advance(0.0);
+        assert_eq!((moving.dx, moving.dy), (0.0, 0.0)); //held still
+
+        let mut spinning = MovingElement::new(ArenaElementMotion::Rotate {speed: 90.0}, 0.0, 0.0, 0.0);
+        assert_eq!(spinning.advance(1.0).2, 90.0);
+        assert_eq!(spinning.spin, 90.0_f32.to_radians());
+    }
+
+    #[test]
+    fn destructible_wall_damage_states() {
+        let mut wall = DestructibleWall::new(3, 90.0);
+        assert_eq!(wall.damage_state(), 0);
+
+        wall.health.damage(29.0);
+        assert_eq!(wall.damage_state(), 0);
+        wall.health.damage(2.0);
+        assert_eq!(wall.damage_state(), 1);
+        wall.health.damage(30.0);
+        assert_eq!(wall.damage_state(), 2);
+
+        wall.health.damage(100.0);
+        assert!(!wall.health.is_alive());
+        assert_eq!(wall.damage_state(), WALL_DAMAGE_STATES - 1);
+    }
+
+    #[test]
+    fn teleporter_turns_velocity_by_relative_rotation() {
+        let entry = Teleporter::new(1, 0.0);
+        let exit = Teleporter::new(1, 90.0);
+
+        let (turn, dx, dy) = entry.turn_to(&exit, 10.0, 0.0);
+        assert!((turn - std::f32::consts::FRAC_PI_2).abs() < 0.0001);
+        assert!(dx.abs() < 0.0001 && (dy - 10.0).abs() < 0.0001);
+
+        let (turn, dx, dy) = entry.turn_to(&Teleporter::new(1, 0.0), 3.0, -4.0);
+        assert_eq!((turn, dx, dy), (0.0, 3.0, -4.0));
+    }
+}
@@ -0,0 +1,238 @@
+use serde::Deserialize;
+
+use super::arena_validation::Footprint;
+use super::{
+    Arena, ArenaElement, ArenaElementHitbox, ArenaElementKinds, ArenaFloor, HitboxProperties, HitboxShape,
+    PlayerSpawnPoint};
+
+
+const OUTER_WALL_THICKNESS: f32 = 20.0;
+const OBSTACLE_AREA: f32 = 10000.0; //arena area per obstacle at full density
+const OBSTACLE_GAP: f32 = 20.0; //kept clear around every obstacle, wide enough for the heaviest ship
+const SPAWN_GAP: f32 = 30.0; //kept clear around spawn points
+const PLACEMENT_ATTEMPTS: usize = 20;
+
+
+#[derive(Copy, Clone, Debug, PartialEq, Deserialize)]
+pub enum ArenaSymmetry {
+    Mirror, //mirrored across both center lines, every obstacle appears four times
+    Rotational, //turned half way around the center, every obstacle appears twice
+}
+
+// Parameters for ArenaNames::Procedural arenas, the seed comes from the arena name
+#[derive(Copy, Clone, Debug, PartialEq, Deserialize)]
+pub struct ProceduralArenaProperties {
+    pub width: f32,
+    pub height: f32,
+    pub density: f32, //0 for an empty arena, 1 for one obstacle per 100x100 area
+    pub symmetry: ArenaSymmetry,
+}
+
+impl Default for ProceduralArenaProperties {
+    fn default() -> ProceduralArenaProperties {
+        ProceduralArenaProperties {
+            width: 400.0,
+            height: 400.0,
+            density: 0.5,
+            symmetry: ArenaSymmetry::Mirror,
+        }
+    }
+}
+
+
+// xorshift64*, small and the same on every platform so a seed always builds the same arena
+struct ArenaRng {
+    state: u64,
+}
+
+impl ArenaRng {
+    fn new(seed: u64) -> ArenaRng {
+        ArenaRng {
+            state: seed.wrapping_add(0x9E37_79B9_7F4A_7C15).max(1),
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    // in [min, max)
+    fn range(&mut self, min: f32, max: f32) -> f32 {
+        let unit = (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32;
+        min + (max - min) * unit
+    }
+
+    // whole numbers keep dumped arenas easy to tune by hand
+    fn range_rounded(&mut self, min: f32, max: f32) -> f32 {
+        self.range(min, max).round()
+    }
+
+    fn chance(&mut self, odds: f32) -> bool {
+        self.range(0.0, 1.0) < odds
+    }
+}
+
+
+fn element(kind: ArenaElementKinds, x: f32, y: f32, rotation: i16, shape: HitboxShape, width: f32, height: f32)
+    -> ArenaElementHitbox
+{
+    ArenaElementHitbox {
+        element: ArenaElement {kind, x, y, rotation},
+        hitbox: HitboxProperties {width, height, shape},
+        motion: None,
+        health: None,
+    }
+}
+
+// Facing the arena center, 0 degrees is up
+fn facing_center(x: f32, y: f32, width: f32, height: f32) -> f32 {
+    (-(width / 2.0 - x)).atan2(height / 2.0 - y).to_degrees().round()
+}
+
+// The symmetric copies of a point, the point itself first
+fn symmetric_points(x: f32, y: f32, width: f32, height: f32, symmetry: ArenaSymmetry) -> Vec<(f32, f32)> {
+    match symmetry {
+        ArenaSymmetry::Mirror => vec![(x, y), (width - x, y), (x, height - y), (width - x, height - y)],
+        ArenaSymmetry::Rotational => vec![(x, y), (width - x, height - y)],
+    }
+}
+
+
+// Builds an arena from a seed: outer walls, a full floor, four spawn points in symmetric corners
+// and symmetric walls and circle obstacles that keep clear of each other and of the spawns.
+pub fn generate_arena(seed: u64, properties: &ProceduralArenaProperties) -> Arena {
+    let mut rng = ArenaRng::new(seed);
+
+    let width = properties.width;
+    let height = properties.height;
+    let symmetry = properties.symmetry;
+
+    let mut arena_elements = vec![
+        element(ArenaElementKinds::Wall, -OUTER_WALL_THICKNESS / 2.0, height / 2.0, 0,
+            HitboxShape::Rectangle, OUTER_WALL_THICKNESS, height),
+        element(ArenaElementKinds::Wall, width + OUTER_WALL_THICKNESS / 2.0, height / 2.0, 0,
+            HitboxShape::Rectangle, OUTER_WALL_THICKNESS, height),
+        element(ArenaElementKinds::Wall, width / 2.0, -OUTER_WALL_THICKNESS / 2.0, 90,
+            HitboxShape::Rectangle, OUTER_WALL_THICKNESS, width),
+        element(ArenaElementKinds::Wall, width / 2.0, height + OUTER_WALL_THICKNESS / 2.0, 90,
+            HitboxShape::Rectangle, OUTER_WALL_THICKNESS, width),
+    ];
+
+    // in player order, each player's opposite is the next or previous one
+    let spawn_x = rng.range_rounded(width * 0.15, width * 0.25);
+    let spawn_y = rng.range_rounded(height * 0.15, height * 0.25);
+    let player_spawn_points: Vec<PlayerSpawnPoint> = [
+        (spawn_x, spawn_y),
+        (width - spawn_x, height - spawn_y),
+        (spawn_x, height - spawn_y),
+        (width - spawn_x, spawn_y),
+    ]
+        .iter()
+        .map(|(x, y)| PlayerSpawnPoint {x: *x, y: *y, rotation: facing_center(*x, *y, width, height)})
+        .collect();
+
+    // obstacles are placed in the first part of the arena and copied into the others
+    let copies = symmetric_points(0.0, 0.0, width, height, symmetry).len();
+    let (region_width, region_height) = match symmetry {
+        ArenaSymmetry::Mirror => (width / 2.0, height / 2.0),
+        ArenaSymmetry::Rotational => (width, height / 2.0),
+    };
+    let obstacles = properties.density.clamp(0.0, 1.0) * width * height / OBSTACLE_AREA;
+    let groups = (obstacles / copies as f32).round() as usize;
+
+    for _ in 0..groups {
+        for _ in 0..PLACEMENT_ATTEMPTS {
+            let x = rng.range_rounded(0.0, region_width);
+            let y = rng.range_rounded(0.0, region_height);
+
+            let (shape, rotation, hitbox_width, hitbox_height) = if rng.chance(0.5) {
+                let diameter = rng.range_rounded(20.0, 60.0);
+                (HitboxShape::Circle, 0, diameter, diameter)
+            } else {
+                let rotation = if rng.chance(0.5) { 0 } else { 90 };
+                (HitboxShape::Rectangle, rotation, 20.0, rng.range_rounded(40.0, 120.0))
+            };
+
+            let group: Vec<ArenaElementHitbox> = symmetric_points(x, y, width, height, symmetry)
+                .iter()
+                .map(|(x, y)| element(ArenaElementKinds::Wall, *x, *y, rotation, shape, hitbox_width, hitbox_height))
+                .collect();
+
+            let footprints: Vec<Footprint> = group.iter().map(Footprint::from_element).collect();
+
+            let blocked = footprints.iter().enumerate().any(|(index, footprint)| {
+                let padded = footprint.inflated(OBSTACLE_GAP);
+
+                arena_elements.iter().any(|placed| padded.overlaps(&Footprint::from_element(placed)))
+                    || footprints.iter().skip(index + 1).any(|copy| padded.overlaps(copy))
+                    || player_spawn_points.iter().any(|spawn| footprint.distance_to(spawn.x, spawn.y) < SPAWN_GAP)
+            });
+
+            if !blocked {
+                arena_elements.extend(group);
+                break;
+            }
+        }
+    }
+
+    Arena {
+        width,
+        height,
+        floor: vec![ArenaFloor {x: width / 2.0, y: height / 2.0, width, height}],
+        player_spawn_points,
+        arena_elements,
+        ..Default::default()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::components::validate_arena;
+
+    fn properties(density: f32, symmetry: ArenaSymmetry) -> ProceduralArenaProperties {
+        ProceduralArenaProperties {
+            density,
+            symmetry,
+            ..ProceduralArenaProperties::default()
+        }
+    }
+
+    #[test]
+    fn same_seed_same_arena() {
+        let properties = ProceduralArenaProperties::default();
+
+        assert_eq!(generate_arena(7, &properties), generate_arena(7, &properties));
+        assert_ne!(generate_arena(7, &properties), generate_arena(8, &properties));
+    }
+
+    #[test]
+    fn generated_arenas_are_valid() {
+        for symmetry in [ArenaSymmetry::Mirror, ArenaSymmetry::Rotational].iter() {
+            for density in [0.0, 0.5, 1.0].iter() {
+                for seed in 0..40 {
+                    let arena = generate_arena(seed, &properties(*density, *symmetry));
+                    assert_eq!(validate_arena(&arena), vec![], "seed {} {:?} {}", seed, symmetry, density);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn obstacles_follow_the_symmetry() {
+        let arena = generate_arena(3, &properties(1.0, ArenaSymmetry::Rotational));
+        assert!(arena.arena_elements.len() > 4);
+
+        for piece in arena.arena_elements.iter() {
+            let (x, y) = (arena.width - piece.element.x, arena.height - piece.element.y);
+            assert!(arena.arena_elements.iter().any(|other| {
+                (other.element.x - x).abs() < 0.01 && (other.element.y - y).abs() < 0.01 && other.hitbox == piece.hitbox
+            }));
+        }
+    }
+}
@@ -0,0 +1,517 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::components::{
+    Arena, ArenaElementHitbox, ArenaElementKinds, ArenaElementMotion, ArenaNames, HitboxShape, MAX_PLAYERS};
+
+
+const SPAWN_CLEARANCE: f32 = 6.0; //a little over the largest ship's radius
+const FLOOR_SAMPLE_STEP: f32 = 10.0; //grid spacing used to look for uncovered floor
+
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ArenaIssueLevel {
+    Error,
+    Warning,
+}
+
+// One problem found in an arena, the message names the offending entries by index
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArenaIssue {
+    pub level: ArenaIssueLevel,
+    pub message: String,
+}
+
+impl ArenaIssue {
+    fn error(message: String) -> ArenaIssue {
+        ArenaIssue {level: ArenaIssueLevel::Error, message}
+    }
+
+    fn warning(message: String) -> ArenaIssue {
+        ArenaIssue {level: ArenaIssueLevel::Warning, message}
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.level == ArenaIssueLevel::Error
+    }
+}
+
+impl fmt::Display for ArenaIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.level {
+            ArenaIssueLevel::Error => write!(f, "error: {}", self.message),
+            ArenaIssueLevel::Warning => write!(f, "warning: {}", self.message),
+        }
+    }
+}
+
+
+// An element's hitbox as placed in the arena, rectangles are axis aligned like at spawn.
+// Also used by the arena generator to keep its pieces apart.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Footprint {
+    Rectangle {x: f32, y: f32, half_width: f32, half_height: f32},
+    Circle {x: f32, y: f32, radius: f32},
+}
+
+impl Footprint {
+    pub fn from_element(arena_element: &ArenaElementHitbox) -> Footprint {
+        let (x, y) = (arena_element.element.x, arena_element.element.y);
+
+        match arena_element.hitbox.shape {
+            HitboxShape::Circle => Footprint::Circle {x, y, radius: arena_element.hitbox.width / 2.0},
+            HitboxShape::Rectangle => {
+                let (width, height) = match arena_element.element.rotation {
+                    90 | 270 => (arena_element.hitbox.height, arena_element.hitbox.width),
+                    _ => (arena_element.hitbox.width, arena_element.hitbox.height),
+                };
+                Footprint::Rectangle {x, y, half_width: width / 2.0, half_height: height / 2.0}
+            },
+        }
+    }
+
+    // Everywhere the element reaches over its motion script, the placement itself if it doesn't move.
+    // Turning elements cover a circle, sliding ones the box around both ends of the slide.
+    pub fn swept(arena_element: &ArenaElementHitbox) -> Footprint {
+        let footprint = Footprint::from_element(arena_element);
+
+        let (slide_x, slide_y) = match arena_element.motion {
+            None => return footprint,
+            Some(ArenaElementMotion::Rotate {..}) => {
+                let (x, y, radius) = match footprint {
+                    Footprint::Circle {x, y, radius} => (x, y, radius),
+                    Footprint::Rectangle {x, y, half_width, half_height} => {
+                        (x, y, (half_width.powi(2) + half_height.powi(2)).sqrt())
+                    },
+                };
+                return Footprint::Circle {x, y, radius};
+            },
+            Some(ArenaElementMotion::Linear {dx, dy, ..}) | Some(ArenaElementMotion::Door {dx, dy, ..}) => (dx, dy),
+        };
+
+        let (x, y, half_width, half_height) = match footprint {
+            Footprint::Circle {x, y, radius} => (x, y, radius, radius),
+            Footprint::Rectangle {x, y, half_width, half_height} => (x, y, half_width, half_height),
+        };
+        Footprint::Rectangle {
+            x: x + slide_x / 2.0,
+            y: y + slide_y / 2.0,
+            half_width: half_width + slide_x.abs() / 2.0,
+            half_height: half_height + slide_y.abs() / 2.0,
+        }
+    }
+
+    // Distance from the point to the footprint's edge, negative inside
+    pub fn distance_to(&self, point_x: f32, point_y: f32) -> f32 {
+        match *self {
+            Footprint::Circle {x, y, radius} => ((point_x - x).powi(2) + (point_y - y).powi(2)).sqrt() - radius,
+            Footprint::Rectangle {x, y, half_width, half_height} => {
+                let dx = (point_x - x).abs() - half_width;
+                let dy = (point_y - y).abs() - half_height;
+
+                if dx > 0.0 || dy > 0.0 {
+                    (dx.max(0.0).powi(2) + dy.max(0.0).powi(2)).sqrt()
+                } else {
+                    dx.max(dy)
+                }
+            },
+        }
+    }
+
+    // Grown on every side, to check for a gap between footprints
+    pub fn inflated(&self, margin: f32) -> Footprint {
+        match *self {
+            Footprint::Rectangle {x, y, half_width, half_height} => {
+                Footprint::Rectangle {x, y, half_width: half_width + margin, half_height: half_height + margin}
+            },
+            Footprint::Circle {x, y, radius} => Footprint::Circle {x, y, radius: radius + margin},
+        }
+    }
+
+    // Touching edges don't count, walls are expected to meet at corners
+    pub fn overlaps(&self, other: &Footprint) -> bool {
+        match (*self, *other) {
+            (Footprint::Rectangle {x, y, half_width, half_height},
+                Footprint::Rectangle {x: other_x, y: other_y, half_width: other_half_width, half_height: other_half_height}) => {
+                (x - other_x).abs() < half_width + other_half_width
+                    && (y - other_y).abs() < half_height + other_half_height
+            },
+            (Footprint::Circle {x, y, radius}, other) | (other, Footprint::Circle {x, y, radius}) => {
+                other.distance_to(x, y) < radius
+            },
+        }
+    }
+}
+
+
+// Checks an arena for mistakes that would otherwise only show up in play.
+// Errors break the arena, warnings are likely mistakes.
+pub fn validate_arena(arena: &Arena) -> Vec<ArenaIssue> {
+    let mut issues: Vec<ArenaIssue> = Vec::new();
+
+    if arena.width <= 0.0 || arena.height <= 0.0 {
+        issues.push(ArenaIssue::error(format!("arena size {}x{} is not positive", arena.width, arena.height)));
+        return issues;
+    }
+
+    // elements
+    let mut walls: Vec<(usize, Footprint)> = Vec::new();
+    let mut zones: Vec<(usize, Footprint)> = Vec::new();
+    let mut sweeps: Vec<(usize, Footprint)> = Vec::new(); //moving walls
+    let mut teleporters: Vec<(usize, u32)> = Vec::new(); //with their pair id
+
+    for (index, arena_element) in arena.arena_elements.iter().enumerate() {
+        let kind = arena_element.element.kind;
+
+        if arena_element.hitbox.width <= 0.0 || arena_element.hitbox.height <= 0.0 {
+            let message = format!("arena_elements[{}]: {:?} has no hitbox ({}x{})",
+                index, kind, arena_element.hitbox.width, arena_element.hitbox.height);
+
+            match kind {
+                ArenaElementKinds::Open => issues.push(ArenaIssue::warning(message)),
+                _ => issues.push(ArenaIssue::error(message)),
+            }
+            continue;
+        }
+
+        if arena_element.hitbox.shape == HitboxShape::Rectangle && arena_element.element.rotation % 90 != 0 {
+            issues.push(ArenaIssue::warning(format!(
+                "arena_elements[{}]: rotation {} is not a multiple of 90, the hitbox stays axis aligned",
+                index, arena_element.element.rotation)));
+        }
+
+        match arena_element.motion {
+            Some(ArenaElementMotion::Linear {period, ..}) if period <= 0.0 => {
+                issues.push(ArenaIssue::error(format!(
+                    "arena_elements[{}]: linear motion period {} is not positive", index, period)));
+            },
+            Some(ArenaElementMotion::Door {closed_time, open_time, slide_time, ..})
+                if closed_time < 0.0 || open_time < 0.0 || slide_time <= 0.0 =>
+            {
+                issues.push(ArenaIssue::error(format!(
+                    "arena_elements[{}]: door times {}/{}/{} need a positive slide and no negative waits",
+                    index, closed_time, open_time, slide_time)));
+            },
+            _ => {},
+        }
+
+        match (kind, arena_element.health) {
+            (_, None) => {},
+            (ArenaElementKinds::Wall, Some(health)) if health <= 0.0 => {
+                issues.push(ArenaIssue::error(format!(
+                    "arena_elements[{}]: wall health {} is not positive", index, health)));
+            },
+            (ArenaElementKinds::Wall, Some(_)) => {},
+            (_, Some(_)) => {
+                issues.push(ArenaIssue::warning(format!(
+                    "arena_elements[{}]: {:?} has health, only walls can be shot down", index, kind)));
+            },
+        }
+
+        if let (ArenaElementKinds::Wall, Some(_)) = (kind, arena_element.motion) {
+            sweeps.push((index, Footprint::swept(arena_element)));
+        }
+
+        match kind {
+            ArenaElementKinds::Wall => walls.push((index, Footprint::from_element(arena_element))),
+            ArenaElementKinds::Zone => zones.push((index, Footprint::from_element(arena_element))),
+            ArenaElementKinds::Teleporter(pair_id) => teleporters.push((index, pair_id)),
+            ArenaElementKinds::Open => {},
+        }
+    }
+
+    for (index, pair_id) in teleporters.iter() {
+        let linked = teleporters.iter().filter(|(_, other_pair_id)| other_pair_id == pair_id).count();
+
+        match linked {
+            1 => issues.push(ArenaIssue::error(format!(
+                "arena_elements[{}]: teleporter pair {} has no partner", index, pair_id))),
+            2 => {},
+            _ => issues.push(ArenaIssue::error(format!(
+                "arena_elements[{}]: teleporter pair {} is shared by {} teleporters", index, pair_id, linked))),
+        }
+    }
+
+    for (place, (index, wall)) in walls.iter().enumerate() {
+        for (other_index, other_wall) in walls.iter().skip(place + 1) {
+            if wall.overlaps(other_wall) {
+                issues.push(ArenaIssue::warning(format!(
+                    "arena_elements[{}]: wall overlaps wall arena_elements[{}]", index, other_index)));
+            }
+        }
+    }
+
+    for (index, zone) in zones.iter() {
+        for (wall_index, wall) in walls.iter() {
+            if zone.overlaps(wall) {
+                issues.push(ArenaIssue::warning(format!(
+                    "arena_elements[{}]: zone overlaps wall arena_elements[{}]", index, wall_index)));
+            }
+        }
+    }
+
+    // the first wall the point is in, and the first one it is too close to
+    let inside_wall = |x: f32, y: f32| walls.iter().find(|(_, wall)| wall.distance_to(x, y) < 0.0).map(|(index, _)| *index);
+    let in_sweep = |x: f32, y: f32| sweeps.iter().find(|(_, sweep)| sweep.distance_to(x, y) < 0.0).map(|(index, _)| *index);
+    let near_wall = |x: f32, y: f32| {
+        walls.iter().chain(sweeps.iter())
+            .find(|(_, wall)| wall.distance_to(x, y) < SPAWN_CLEARANCE)
+            .map(|(index, _)| *index)
+    };
+    let inside_zone = |x: f32, y: f32| zones.iter().find(|(_, zone)| zone.distance_to(x, y) < 0.0).map(|(index, _)| *index);
+    let in_bounds = |x: f32, y: f32| x >= 0.0 && x <= arena.width && y >= 0.0 && y <= arena.height;
+
+    let check_point = |issues: &mut Vec<ArenaIssue>, name: String, x: f32, y: f32, clearance: bool| {
+        if !in_bounds(x, y) {
+            issues.push(ArenaIssue::error(format!("{}: ({}, {}) is outside the arena", name, x, y)));
+        } else if let Some(wall_index) = inside_wall(x, y) {
+            issues.push(ArenaIssue::error(format!("{}: ({}, {}) is inside wall arena_elements[{}]", name, x, y, wall_index)));
+        } else if let Some(wall_index) = in_sweep(x, y) {
+            issues.push(ArenaIssue::error(format!(
+                "{}: ({}, {}) is in the path of moving wall arena_elements[{}]", name, x, y, wall_index)));
+        } else if let (true, Some(wall_index)) = (clearance, near_wall(x, y)) {
+            issues.push(ArenaIssue::warning(format!(
+                "{}: ({}, {}) is too close to wall arena_elements[{}] to spawn a ship", name, x, y, wall_index)));
+        }
+    };
+
+    // ships spawning on a zone would score or capture it without contest
+    let check_spawn_zone = |issues: &mut Vec<ArenaIssue>, name: String, x: f32, y: f32| {
+        if let Some(zone_index) = inside_zone(x, y) {
+            issues.push(ArenaIssue::error(format!(
+                "{}: ({}, {}) is inside zone arena_elements[{}]", name, x, y, zone_index)));
+        }
+    };
+
+    // spawns and markers
+    if arena.player_spawn_points.len() < MAX_PLAYERS {
+        issues.push(ArenaIssue::warning(format!("only {} of {} player spawn points",
+            arena.player_spawn_points.len(), MAX_PLAYERS)));
+    }
+
+    for (index, spawn_point) in arena.player_spawn_points.iter().enumerate() {
+        check_point(&mut issues, format!("player_spawn_points[{}]", index), spawn_point.x, spawn_point.y, true);
+        check_spawn_zone(&mut issues, format!("player_spawn_points[{}]", index), spawn_point.x, spawn_point.y);
+    }
+
+    // teammates are spread along the longer side, check every place for any team size
+    for (index, team_spawn) in arena.team_spawns.iter().enumerate() {
+        let mut offsets: Vec<f32> = (1..=MAX_PLAYERS).flat_map(|teammates| {
+            (0..teammates).map(move |place| (place as f32 + 0.5) / teammates as f32 - 0.5)
+        }).collect();
+        offsets.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        offsets.dedup();
+
+        for offset in offsets {
+            let (x, y) = if team_spawn.width >= team_spawn.height {
+                (team_spawn.x + offset * team_spawn.width, team_spawn.y)
+            } else {
+                (team_spawn.x, team_spawn.y + offset * team_spawn.height)
+            };
+            check_point(&mut issues, format!("team_spawns[{}]", index), x, y, true);
+            check_spawn_zone(&mut issues, format!("team_spawns[{}]", index), x, y);
+        }
+    }
+
+    // ships come out on the center of the partner pad
+    for (index, _) in teleporters.iter() {
+        let teleporter = &arena.arena_elements[*index].element;
+        check_point(&mut issues, format!("arena_elements[{}]", index), teleporter.x, teleporter.y, true);
+    }
+
+    for (index, pickup_spawn) in arena.pickup_spawns.iter().enumerate() {
+        check_point(&mut issues, format!("pickup_spawns[{}]", index), pickup_spawn.x, pickup_spawn.y, false);
+
+        if pickup_spawn.kinds.is_empty() {
+            issues.push(ArenaIssue::error(format!("pickup_spawns[{}]: no pickup kinds", index)));
+        }
+    }
+
+    for (index, flag) in arena.flags.iter().enumerate() {
+        check_point(&mut issues, format!("flags[{}]", index), flag.x, flag.y, false);
+    }
+
+    // a lap starts past the start/finish line, so a course needs at least one other checkpoint
+    if arena.checkpoints.len() == 1 {
+        issues.push(ArenaIssue::error(
+            "checkpoints: a race course needs at least 2 checkpoints, only the start/finish line is set".to_string()));
+    }
+
+    for (index, checkpoint) in arena.checkpoints.iter().enumerate() {
+        check_point(&mut issues, format!("checkpoints[{}]", index), checkpoint.x, checkpoint.y, false);
+
+        if checkpoint.width <= 0.0 || checkpoint.height <= 0.0 {
+            issues.push(ArenaIssue::error(format!("checkpoints[{}]: has no area ({}x{})",
+                index, checkpoint.width, checkpoint.height)));
+        }
+    }
+
+    // floor coverage, sampled on a grid over the open parts of the arena
+    let mut uncovered: Vec<(f32, f32)> = Vec::new();
+    let mut y = FLOOR_SAMPLE_STEP / 2.0;
+    while y < arena.height {
+        let mut x = FLOOR_SAMPLE_STEP / 2.0;
+        while x < arena.width {
+            let covered = arena.floor.iter().any(|floor| {
+                (x - floor.x).abs() <= floor.width / 2.0 && (y - floor.y).abs() <= floor.height / 2.0
+            });
+
+            if !covered && inside_wall(x, y).is_none() {
+                uncovered.push((x, y));
+            }
+            x += FLOOR_SAMPLE_STEP;
+        }
+        y += FLOOR_SAMPLE_STEP;
+    }
+
+    if let Some((x, y)) = uncovered.first() {
+        issues.push(ArenaIssue::warning(format!(
+            "floor does not cover the playable area, {} uncovered samples starting at ({}, {})",
+            uncovered.len(), x, y)));
+    }
+
+    issues
+}
+
+// Every arena in the store, in name order so reports are stable
+pub fn validate_arenas(arenas: &HashMap<ArenaNames, Arena>) -> Vec<(ArenaNames, Vec<ArenaIssue>)> {
+    let mut results: Vec<(ArenaNames, Vec<ArenaIssue>)> = arenas
+        .iter()
+        .map(|(arena_name, arena)| (*arena_name, validate_arena(arena)))
+        .collect();
+
+    results.sort_by_key(|(arena_name, _)| format!("{:?}", arena_name));
+    results
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::components::{
+        ArenaCheckpoint, ArenaElement, ArenaFloor, HitboxProperties, PlayerSpawnPoint};
+
+    fn element(kind: ArenaElementKinds, x: f32, y: f32, shape: HitboxShape, width: f32, height: f32) -> ArenaElementHitbox {
+        ArenaElementHitbox {
+            element: ArenaElement {kind, x, y, rotation: 0},
+            hitbox: HitboxProperties {width, height, shape},
+            motion: None,
+            health: None,
+        }
+    }
+
+    fn open_arena() -> Arena {
+        Arena {
+            width: 100.0,
+            height: 100.0,
+            floor: vec![ArenaFloor {x: 50.0, y: 50.0, width: 100.0, height: 100.0}],
+            player_spawn_points: (0..MAX_PLAYERS)
+                .map(|id| PlayerSpawnPoint {x: 20.0 + 20.0 * id as f32, y: 20.0, rotation: 0.0})
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn shipped_arenas_have_no_errors() {
+        let file = std::fs::File::open("assets/game/arena_properties.ron").expect("Failed to open arenas");
+        let arenas: HashMap<ArenaNames, Arena> = ron::de::from_reader(file).expect("Failed to load arenas");
+
+        for (arena_name, issues) in validate_arenas(&arenas) {
+            let errors: Vec<String> = issues.iter().filter(|issue| issue.is_error()).map(|issue| issue.to_string()).collect();
+            assert!(errors.is_empty(), "{:?}: {:?}", arena_name, errors);
+        }
+    }
+
+    #[test]
+    fn open_arena_is_clean() {
+        assert_eq!(validate_arena(&open_arena()), vec![]);
+    }
+
+    #[test]
+    fn reports_spawns_in_walls_and_empty_zones() {
+        let mut arena = open_arena();
+        arena.arena_elements = vec![
+            element(ArenaElementKinds::Wall, 40.0, 20.0, HitboxShape::Circle, 10.0, 10.0),
+            element(ArenaElementKinds::Zone, 70.0, 70.0, HitboxShape::Rectangle, 0.0, 20.0),
+        ];
+
+        let issues = validate_arena(&arena);
+
+        assert!(issues.contains(&ArenaIssue::error(
+            "player_spawn_points[1]: (40, 20) is inside wall arena_elements[0]".to_string())));
+        assert!(issues.contains(&ArenaIssue::error("arena_elements[1]: Zone has no hitbox (0x20)".to_string())));
+    }
+
+    #[test]
+    fn reports_spawns_inside_zones() {
+        let mut arena = open_arena();
+        arena.arena_elements = vec![
+            element(ArenaElementKinds::Zone, 40.0, 20.0, HitboxShape::Rectangle, 30.0, 30.0),
+        ];
+
+        assert_eq!(validate_arena(&arena), vec![ArenaIssue::error(
+            "player_spawn_points[1]: (40, 20) is inside zone arena_elements[0]".to_string())]);
+    }
+
+    #[test]
+    fn warns_on_overlapping_walls_and_missing_floor() {
+        let mut arena = open_arena();
+        arena.floor[0].width = 50.0;
+        arena.arena_elements = vec![
+            element(ArenaElementKinds::Wall, 60.0, 60.0, HitboxShape::Rectangle, 20.0, 20.0),
+            element(ArenaElementKinds::Wall, 75.0, 60.0, HitboxShape::Circle, 20.0, 20.0),
+            element(ArenaElementKinds::Wall, 80.0, 80.0, HitboxShape::Rectangle, 20.0, 20.0), //touches the first corner only
+        ];
+
+        let issues = validate_arena(&arena);
+        let warnings: Vec<&ArenaIssue> = issues.iter().filter(|issue| !issue.is_error()).collect();
+
+        assert!(issues.iter().all(|issue| !issue.is_error()));
+        assert_eq!(warnings[0].message, "arena_elements[0]: wall overlaps wall arena_elements[1]");
+        assert!(warnings[1].message.starts_with("floor does not cover the playable area"));
+        assert_eq!(warnings.len(), 2);
+    }
+
+    #[test]
+    fn reports_spawns_in_the_path_of_moving_walls() {
+        let mut arena = open_arena();
+        let mut sliding = element(ArenaElementKinds::Wall, 60.0, 60.0, HitboxShape::Rectangle, 10.0, 10.0);
+        sliding.motion = Some(ArenaElementMotion::Linear {dx: 0.0, dy: -40.0, period: 0.0});
+        arena.arena_elements = vec![sliding];
+
+        let issues = validate_arena(&arena);
+
+        assert!(issues.contains(&ArenaIssue::error(
+            "arena_elements[0]: linear motion period 0 is not positive".to_string())));
+        assert!(issues.contains(&ArenaIssue::error(
+            "player_spawn_points[2]: (60, 20) is in the path of moving wall arena_elements[0]".to_string())));
+        assert_eq!(issues.len(), 2);
+    }
+
+    #[test]
+    fn reports_unpaired_teleporters() {
+        let mut arena = open_arena();
+        arena.arena_elements = vec![
+            element(ArenaElementKinds::Teleporter(1), 20.0, 80.0, HitboxShape::Circle, 20.0, 20.0),
+            element(ArenaElementKinds::Teleporter(1), 80.0, 80.0, HitboxShape::Circle, 20.0, 20.0),
+            element(ArenaElementKinds::Teleporter(2), 50.0, 60.0, HitboxShape::Circle, 20.0, 20.0),
+        ];
+
+        assert_eq!(validate_arena(&arena), vec![ArenaIssue::error(
+            "arena_elements[2]: teleporter pair 2 has no partner".to_string())]);
+    }
+
+    #[test]
+    fn reports_race_course_without_a_second_checkpoint() {
+        let mut arena = open_arena();
+        arena.checkpoints = vec![ArenaCheckpoint {x: 50.0, y: 50.0, width: 10.0, height: 30.0, rotation: 0}];
+
+        assert_eq!(validate_arena(&arena), vec![ArenaIssue::error(
+            "checkpoints: a race course needs at least 2 checkpoints, only the start/finish line is set".to_string())]);
+
+        arena.checkpoints.push(ArenaCheckpoint {x: 50.0, y: 80.0, width: 10.0, height: 30.0, rotation: 0});
+        assert_eq!(validate_arena(&arena), vec![]);
+    }
+}
@@ -19,6 +19,10 @@ impl Component for CameraOrthoEdges {
 
 const CAMERA_ZOOM_RATE: f32 = 120.0;
 
+//the extra space the camera gives around the players, as in the old rally_game project
+pub const CAMERA_BUFFER: f32 = 80.0;
+pub const CAMERA_BUFFER_RACE: f32 = 160.0; //more room to see the course ahead
+
 impl CameraOrthoEdges {
     pub fn init_edges_keeping_aspect_ratio(
         &mut self,
@@ -111,12 +115,11 @@ impl CameraPlayerBounds {
         }
     }
 
-    fn add_arena_buffer(&mut self, arena_width: f32, arena_height: f32) {
+    fn add_arena_buffer(&mut self, arena_width: f32, arena_height: f32, arena_buffer: f32) {
         //this is the extra buffer space that the camera gives
-        //offset was 80.0 for combat and 160.0 for race mode in old rally_game project
         if self.xs.len() > 0 {
-            let dx_offset = 80.0 + 1.0 * self.max_abs_dx;
-            let dy_offset = 80.0 + 1.0 * self.max_abs_dy;
+            let dx_offset = arena_buffer + 1.0 * self.max_abs_dx;
+            let dy_offset = arena_buffer + 1.0 * self.max_abs_dy;
 
             self.min_x = (self.min_x - dx_offset).max(0.0);
             self.max_x = (self.max_x + dx_offset).min(arena_width);
@@ -125,12 +128,13 @@ impl CameraPlayerBounds {
         }
     }
 
-    pub fn calc_bounds_on_players_and_arena(&mut self, arena_width: f32, arena_height: f32) {
+    pub fn calc_bounds_on_players_and_arena(&mut self, arena_width: f32, arena_height: f32, arena_buffer: f32) {
         self.calc_player_min_max();
 
         self.add_arena_buffer(
             arena_width,
-            arena_height
+            arena_height,
+            arena_buffer,
         );
     }
 
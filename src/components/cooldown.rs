@@ -28,6 +28,10 @@ impl Cooldown {
         self.timer = self.reset;
     }
 
+    pub fn set_reset(&mut self, reset: f32) {
+        self.reset = reset;
+    }
+
     pub fn timer_reset_multiplier(&mut self, mult: f32) {
         self.timer = self.reset * mult;
     }
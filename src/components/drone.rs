@@ -0,0 +1,210 @@
+use amethyst::ecs::prelude::{Component, DenseVecStorage};
+
+use serde::Deserialize;
+
+use std::collections::HashMap;
+
+use crate::components::WeaponNames;
+
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Deserialize)]
+pub enum DroneKinds {
+    Scout,
+    Gunner,
+    Rammer,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Deserialize)]
+pub struct DroneProperties {
+    pub health: f32,
+    pub mass: f32,
+    pub max_accel_force: f32,
+    pub max_speed: f32,
+    pub hitbox_diameter: f32,
+    pub tint: (f32, f32, f32, f32),
+    #[serde(default)]
+    pub weapon: Option<WeaponNames>, //unarmed drones ram instead
+    #[serde(default)]
+    pub fire_range: f32, //holds this distance from its target while firing
+    #[serde(default)]
+    pub ram_damage: f32, //per second in contact with a player
+}
+
+impl Default for DroneProperties {
+    fn default() -> DroneProperties {
+        DroneProperties {
+            health: 30.0,
+            mass: 0.8,
+            max_accel_force: 200.0,
+            max_speed: 80.0,
+            hitbox_diameter: 8.0,
+            tint: (0.6, 0.6, 0.6, 1.0),
+            weapon: None,
+            fire_range: 0.0,
+            ram_damage: 20.0,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Deserialize)]
+pub struct WaveSpawn {
+    pub kind: DroneKinds,
+    pub count: u32,
+    #[serde(default)]
+    pub weapon: Option<WeaponNames>, //replaces the drone kind's weapon for this wave
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct WaveProperties {
+    pub spawns: Vec<WaveSpawn>,
+    pub spawn_interval: f32, //seconds between each drone entering
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct SurvivalProperties {
+    pub drones: HashMap<DroneKinds, DroneProperties>,
+    pub waves: Vec<WaveProperties>,
+    pub wave_break: f32, //seconds between clearing a wave and the next one starting
+    pub endless_count_mult: f32, //after the last wave it repeats, with counts multiplied per extra wave
+    pub revive_range: f32,
+    pub revive_time: f32, //seconds a teammate has to stay in range
+    pub revive_health: f32, //fraction of max health a revived player comes back with
+}
+
+impl Default for SurvivalProperties {
+    fn default() -> SurvivalProperties {
+        SurvivalProperties {
+            drones: HashMap::new(),
+            waves: vec![],
+            wave_break: 5.0,
+            endless_count_mult: 1.25,
+            revive_range: 25.0,
+            revive_time: 3.0,
+            revive_health: 0.5,
+        }
+    }
+}
+
+pub struct SurvivalStoreResource {
+    pub properties: SurvivalProperties,
+}
+
+impl SurvivalStoreResource {
+    pub fn get_drone(&self, kind: &DroneKinds) -> DroneProperties {
+        match self.properties.drones.get(kind) {
+            Some(drone_props_get) => *drone_props_get,
+            _ => DroneProperties::default(),
+        }
+    }
+
+    // The drones entering in a wave, in spawn order, counting from wave 0.
+    // Waves past the last defined one repeat it with escalating counts.
+    pub fn get_wave(&self, wave: usize) -> Vec<(DroneKinds, Option<WeaponNames>)> {
+        let last_wave = match self.properties.waves.len() {
+            0 => return vec![],
+            wave_count => wave_count - 1,
+        };
+
+        let count_mult = self.properties.endless_count_mult.powi(wave.saturating_sub(last_wave) as i32);
+
+        let mut drones = vec![];
+        for spawn in self.properties.waves[wave.min(last_wave)].spawns.iter() {
+            let weapon = spawn.weapon.or(self.get_drone(&spawn.kind).weapon);
+            let count = (spawn.count as f32 * count_mult).round() as u32;
+
+            for _ in 0..count {
+                drones.push((spawn.kind, weapon));
+            }
+        }
+        drones
+    }
+
+    pub fn get_spawn_interval(&self, wave: usize) -> f32 {
+        match self.properties.waves.last() {
+            Some(last_wave) => self.properties.waves.get(wave).unwrap_or(last_wave).spawn_interval,
+            None => 0.0,
+        }
+    }
+}
+
+
+#[derive(Debug, PartialEq)]
+pub struct Drone {
+    pub kind: DroneKinds,
+    pub fire_range: f32,
+    pub max_speed: f32,
+    pub ram_damage: f32,
+    last_hit_by: Option<usize>,
+}
+
+impl Component for Drone {
+    type Storage = DenseVecStorage<Self>;
+}
+
+impl Drone {
+    pub fn new(kind: DroneKinds, properties: &DroneProperties) -> Drone {
+        Drone {
+            kind,
+            fire_range: properties.fire_range,
+            max_speed: properties.max_speed,
+            ram_damage: properties.ram_damage,
+            last_hit_by: None,
+        }
+    }
+
+    pub fn hit_by(&mut self, player_id: usize) {
+        self.last_hit_by = Some(player_id);
+    }
+
+    // The player credited when the drone is destroyed
+    pub fn kill_credit(&self) -> Option<usize> {
+        self.last_hit_by
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn waves_escalate_past_the_last() {
+        let mut drones = HashMap::new();
+        drones.insert(DroneKinds::Gunner, DroneProperties {
+            weapon: Some(WeaponNames::ProtonCannon),
+            ..DroneProperties::default()
+        });
+
+        let store = SurvivalStoreResource {
+            properties: SurvivalProperties {
+                drones,
+                waves: vec![
+                    WaveProperties {
+                        spawns: vec![WaveSpawn { kind: DroneKinds::Scout, count: 2, weapon: None }],
+                        spawn_interval: 1.0,
+                    },
+                    WaveProperties {
+                        spawns: vec![
+                            WaveSpawn { kind: DroneKinds::Gunner, count: 2, weapon: None },
+                            WaveSpawn { kind: DroneKinds::Gunner, count: 2, weapon: Some(WeaponNames::RailGun) },
+                        ],
+                        spawn_interval: 0.5,
+                    },
+                ],
+                endless_count_mult: 1.5,
+                ..SurvivalProperties::default()
+            },
+        };
+
+        assert_eq!(store.get_wave(0), vec![(DroneKinds::Scout, None); 2]);
+
+        let wave = store.get_wave(1);
+        assert_eq!(wave.len(), 4);
+        assert_eq!(wave[0], (DroneKinds::Gunner, Some(WeaponNames::ProtonCannon)));
+        assert_eq!(wave[3], (DroneKinds::Gunner, Some(WeaponNames::RailGun)));
+
+        // 2 * 1.5 * 1.5 of each
+        assert_eq!(store.get_wave(3).len(), 10);
+        assert_eq!(store.get_spawn_interval(3), 0.5);
+    }
+}
@@ -0,0 +1,74 @@
+use amethyst::ecs::prelude::{Component, DenseVecStorage};
+
+use crate::components::Cooldown;
+
+
+pub const FLAG_CARRIER_MASS: f32 = 0.5; //added to the carrier's Mass, slowing them down
+pub const FLAG_RETURN_TIME: f32 = 10.0; //seconds a dropped flag waits before returning itself
+
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum FlagStates {
+    AtBase,
+    Carried(usize), //player id
+    Dropped,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Flag {
+    pub team: usize,
+    pub base_x: f32,
+    pub base_y: f32,
+    state: FlagStates,
+    pub return_cooldown: Cooldown,
+}
+
+impl Component for Flag {
+    type Storage = DenseVecStorage<Self>;
+}
+
+impl Flag {
+    pub fn new(team: usize, base_x: f32, base_y: f32) -> Flag {
+        Flag {
+            team,
+            base_x,
+            base_y,
+            state: FlagStates::AtBase,
+            return_cooldown: Cooldown::new(0.0, FLAG_RETURN_TIME),
+        }
+    }
+
+    pub fn get_state(&self) -> FlagStates {
+        self.state
+    }
+
+    pub fn carrier(&self) -> Option<usize> {
+        match self.state {
+            FlagStates::Carried(player_id) => Some(player_id),
+            _ => None,
+        }
+    }
+
+    pub fn pickup(&mut self, player_id: usize) {
+        self.state = FlagStates::Carried(player_id);
+    }
+
+    pub fn set_dropped(&mut self) {
+        self.state = FlagStates::Dropped;
+        self.return_cooldown.timer_reset();
+    }
+
+    pub fn return_to_base(&mut self) {
+        self.state = FlagStates::AtBase;
+    }
+}
+
+
+#[derive(Debug, PartialEq)]
+pub struct FlagBase {
+    pub team: usize,
+}
+
+impl Component for FlagBase {
+    type Storage = DenseVecStorage<Self>;
+}
@@ -0,0 +1,957 @@
+use serde::Deserialize;
+
+use std::collections::HashMap;
+use std::ops::AddAssign;
+
+use crate::components::{ArenaNames, ArenaStoreResource, Handicap, WeaponNames};
+
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Deserialize)]
+pub enum GameModes {
+    ClassicGunGame,
+    DeathmatchKills,
+    DeathmatchStock,
+    DeathmatchTimedKD,
+    KingOfTheHill,
+    CaptureTheFlag,
+    Race,
+    SurvivalWaves,
+}
+
+pub const GAME_MODES: [GameModes; 8] = [
+    GameModes::ClassicGunGame,
+    GameModes::DeathmatchKills,
+    GameModes::DeathmatchStock,
+    GameModes::DeathmatchTimedKD,
+    GameModes::KingOfTheHill,
+    GameModes::CaptureTheFlag,
+    GameModes::Race,
+    GameModes::SurvivalWaves,
+];
+
+impl GameModes {
+    pub fn next(&self) -> GameModes {
+        let index = GAME_MODES.iter().position(|mode| mode == self).unwrap_or(0);
+        GAME_MODES[(index + 1) % GAME_MODES.len()]
+    }
+
+    pub fn prev(&self) -> GameModes {
+        let index = GAME_MODES.iter().position(|mode| mode == self).unwrap_or(0);
+        GAME_MODES[(index + GAME_MODES.len() - 1) % GAME_MODES.len()]
+    }
+}
+
+
+fn default_rounds() -> u32 {
+    1
+}
+
+fn default_respawn_time() -> f32 {
+    3.0
+}
+
+fn default_zone_capture_time() -> f32 {
+    2.0
+}
+
+fn default_laps() -> u32 {
+    3
+}
+
+fn default_round_end_freeze() -> f32 {
+    2.0
+}
+
+fn default_intermission() -> f32 {
+    5.0
+}
+
+fn default_rebuild_walls() -> bool {
+    true
+}
+
+fn default_weapon_ladder() -> Vec<WeaponNames> {
+    vec![WeaponNames::PlasmaRepeater, WeaponNames::ProtonCannon, WeaponNames::HeavyCannon, WeaponNames::RailGun]
+}
+
+// What happens when the time limit runs out on a tie for the lead
+#[derive(Copy, Clone, Debug, PartialEq, Deserialize)]
+pub enum OvertimeRules {
+    Draw,
+    SuddenDeath, //keep playing until the tie is broken
+    Extra(f32), //seconds added once, then a draw if still tied
+}
+
+impl Default for OvertimeRules {
+    fn default() -> OvertimeRules {
+        OvertimeRules::Draw
+    }
+}
+
+// Extra pressure once the round goes to overtime, to force the tie to break
+#[derive(Copy, Clone, Debug, PartialEq, Deserialize)]
+pub enum SuddenDeathRules {
+    Off,
+    OneHitKills, //any damage destroys a ship
+    ShrinkingArena(f32), //seconds for the safe area to close in on the arena center
+}
+
+impl Default for SuddenDeathRules {
+    fn default() -> SuddenDeathRules {
+        SuddenDeathRules::Off
+    }
+}
+
+pub const TEAM_COUNT: usize = 2;
+
+pub const SAFE_ZONE_MIN_SCALE: f32 = 0.15; //the shrinking arena stops at this fraction of its size
+pub const KILL_FEED_TIME: f32 = 5.0; //seconds a kill stays on the HUD
+pub const KILL_FEED_LINES: usize = 4;
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct GameModeSetup {
+    pub arenas: Vec<ArenaNames>, //rotated through, one per round
+    #[serde(default = "default_rounds")]
+    pub rounds: u32, //best-of-N, the match ends early once a player has won the majority
+    #[serde(default = "default_respawn_time")]
+    pub respawn_time: f32, //seconds
+    #[serde(default)]
+    pub lives: Option<u32>, //eliminated once all are lost, None for unlimited respawns
+    #[serde(default)]
+    pub kill_target: Option<u32>, //first to reach it wins the round
+    #[serde(default)]
+    pub time_limit: Option<f32>, //seconds
+    #[serde(default)]
+    pub overtime: OvertimeRules,
+    #[serde(default)]
+    pub sudden_death: SuddenDeathRules,
+    #[serde(default)]
+    pub score_target: Option<f32>, //mode score to win, e.g. seconds holding the hill
+    #[serde(default = "default_zone_capture_time")]
+    pub zone_capture_time: f32, //seconds alone in a zone to capture it
+    #[serde(default)]
+    pub zone_rotation_time: Option<f32>, //seconds before the next zone activates
+    #[serde(default)]
+    pub teams: bool, //players split into two teams, otherwise everyone is their own team
+    #[serde(default = "default_laps")]
+    pub laps: u32,
+    #[serde(default)]
+    pub start_countdown: f32, //seconds players are held on their spawn points before the round starts
+    #[serde(default)]
+    pub revives: bool, //destroyed players are downed until revived instead of respawning
+    #[serde(default = "default_weapon_ladder")]
+    pub weapon_ladder: Vec<WeaponNames>, //gun game order, followed by a final ram tier
+    #[serde(default = "default_round_end_freeze")]
+    pub round_end_freeze: f32, //seconds everything holds still after a round is decided
+    #[serde(default = "default_intermission")]
+    pub intermission: f32, //seconds the scoreboard shows before the next round
+    #[serde(default = "default_rebuild_walls")]
+    pub rebuild_walls: bool, //destroyed walls are back every round, otherwise they stay down for the match
+}
+
+impl Default for GameModeSetup {
+    fn default() -> GameModeSetup {
+        GameModeSetup {
+            arenas: vec![ArenaNames::StandardCombat],
+            rounds: default_rounds(),
+            respawn_time: default_respawn_time(),
+            lives: None,
+            kill_target: None,
+            time_limit: None,
+            overtime: OvertimeRules::default(),
+            sudden_death: SuddenDeathRules::default(),
+            score_target: None,
+            zone_capture_time: default_zone_capture_time(),
+            zone_rotation_time: None,
+            teams: false,
+            laps: default_laps(),
+            start_countdown: 0.0,
+            revives: false,
+            weapon_ladder: default_weapon_ladder(),
+            round_end_freeze: default_round_end_freeze(),
+            intermission: default_intermission(),
+            rebuild_walls: default_rebuild_walls(),
+        }
+    }
+}
+
+pub struct GameModeStoreResource {
+    pub properties: HashMap<GameModes, GameModeSetup>,
+}
+
+impl GameModeStoreResource {
+    pub fn get_setup(&self, game_mode: &GameModes) -> GameModeSetup {
+        match self.properties.get(game_mode) {
+            Some(setup_get) => setup_get.clone(),
+            _ => GameModeSetup::default(),
+        }
+    }
+}
+
+
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub struct PlayerScore {
+    pub kills: u32,
+    pub deaths: u32,
+    pub suicides: u32, //deaths with nobody to credit, also counted in deaths
+    pub score: f32, //mode specific, e.g. hill time or captures
+    pub tier: usize, //gun game ladder position
+}
+
+impl PlayerScore {
+    // Self-destructs and environment deaths cost a kill
+    pub fn kill_score(&self) -> i32 {
+        self.kills as i32 - self.suicides as i32
+    }
+
+    pub fn kd_score(&self) -> i32 {
+        self.kills as i32 - self.deaths as i32
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct KillEvent {
+    pub victim: usize,
+    pub killer: Option<usize>, //None for a suicide, or a death to drones or the arena
+    pub rammed: bool,
+    pub time: f32, //round time of the kill
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum RoundState {
+    Countdown, //players are held in place until the start
+    Playing,
+    RoundOver(Option<usize>), //round winner, None on a draw
+    MatchOver(Option<usize>), //match winner, None on a draw
+}
+
+// Lives across rounds; the game state is rebuilt from this at the start of every round.
+// Each mode's systems decide when a round is won and report it through end_round.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MatchResource {
+    pub game_mode: GameModes,
+    pub setup: GameModeSetup,
+    pub round: u32,
+    pub round_wins: Vec<u32>,
+    pub scores: Vec<PlayerScore>,
+    pub player_teams: Vec<usize>, //team by player id
+    pub handicaps: Vec<Handicap>, //by player id, set in the lobby
+    pub kill_feed: Vec<KillEvent>, //this round, oldest first
+    pub state: RoundState,
+    pub countdown: f32, //seconds left before the round starts
+    pub round_time: f32, //seconds played this round
+    pub round_end_time: f32, //seconds left of the freeze and intermission once the round is decided
+    pub destroyed_walls: Vec<(ArenaNames, usize)>, //arena element indices kept down between rounds
+    overtime_added: f32,
+}
+
+impl MatchResource {
+    pub fn new(game_mode: GameModes, setup: GameModeSetup, player_count: usize) -> MatchResource {
+        let player_teams = if setup.teams {
+            balance_teams(&vec![None; player_count])
+        } else {
+            (0..player_count).collect()
+        };
+
+        let mut match_resource = MatchResource {
+            game_mode,
+            setup,
+            round: 0,
+            round_wins: vec![0; player_count],
+            scores: vec![PlayerScore::default(); player_count],
+            player_teams,
+            handicaps: vec![Handicap::default(); player_count],
+            kill_feed: vec![],
+            state: RoundState::Playing,
+            countdown: 0.0,
+            round_time: 0.0,
+            round_end_time: 0.0,
+            destroyed_walls: vec![],
+            overtime_added: 0.0,
+        };
+        match_resource.start_countdown();
+        match_resource
+    }
+
+    fn start_countdown(&mut self) {
+        self.countdown = self.setup.start_countdown;
+        self.state = if self.countdown > 0.0 {
+            RoundState::Countdown
+        } else {
+            RoundState::Playing
+        };
+    }
+
+    pub fn counting_down(&self) -> bool {
+        self.state == RoundState::Countdown
+    }
+
+    pub fn update_countdown(&mut self, dt: f32) {
+        if self.counting_down() {
+            self.countdown -= dt;
+            if self.countdown <= 0.0 {
+                self.countdown = 0.0;
+                self.state = RoundState::Playing;
+            }
+        }
+    }
+
+    // Ships can't move or fire outside of play
+    pub fn frozen(&self) -> bool {
+        self.state != RoundState::Playing
+    }
+
+    pub fn round_over(&self) -> bool {
+        match self.state {
+            RoundState::RoundOver(_) | RoundState::MatchOver(_) => true,
+            RoundState::Countdown | RoundState::Playing => false,
+        }
+    }
+
+    pub fn update_round_end(&mut self, dt: f32) {
+        if self.round_over() {
+            self.round_end_time = (self.round_end_time - dt).max(0.0);
+        }
+    }
+
+    // The scoreboard follows the round end freeze
+    pub fn in_intermission(&self) -> bool {
+        self.round_over() && self.round_end_time <= self.setup.intermission
+    }
+
+    pub fn ready_for_next_round(&self) -> bool {
+        self.round_over() && self.round_end_time <= 0.0
+    }
+
+    // Rotates through the mode's arena list by round,
+    // skipping any arena that isn't defined in the arena store.
+    pub fn pick_arena(&self, arena_store: &ArenaStoreResource) -> ArenaNames {
+        let arena_count = self.setup.arenas.len();
+
+        for offset in 0..arena_count {
+            let arena_name = self.setup.arenas[(self.round as usize + offset) % arena_count];
+            if arena_store.contains(&arena_name) {
+                return arena_name;
+            }
+        }
+
+        ArenaNames::StandardCombat
+    }
+
+    pub fn round_active(&self) -> bool {
+        self.state == RoundState::Playing
+    }
+
+    pub fn lives_left(&self, player_id: usize) -> Option<u32> {
+        let lives = self.setup.lives?;
+        let deaths = self.scores.get(player_id).map_or(0, |score| score.deaths);
+        Some(lives.saturating_sub(deaths))
+    }
+
+    pub fn get_score_mut(&mut self, player_id: usize) -> Option<&mut PlayerScore> {
+        self.scores.get_mut(player_id)
+    }
+
+    pub fn get_team(&self, player_id: usize) -> usize {
+        *self.player_teams.get(player_id).unwrap_or(&player_id)
+    }
+
+    // Anyone without a lobby pick plays even
+    pub fn get_handicap(&self, player_id: usize) -> Handicap {
+        self.handicaps.get(player_id).cloned().unwrap_or_default()
+    }
+
+    // Damage from a hit, scaled by the attacker's handicap and then the victim's.
+    // None is a drone or the arena, which play even.
+    pub fn scale_damage(&self, damage: f32, attacker_id: Option<usize>, victim_id: Option<usize>) -> f32 {
+        let damage_dealt = attacker_id.map_or(1.0, |attacker_id| self.get_handicap(attacker_id).damage_dealt);
+        let damage_taken = victim_id.map_or(1.0, |victim_id| self.get_handicap(victim_id).damage_taken);
+
+        damage * damage_dealt * damage_taken
+    }
+
+    pub fn respawn_time(&self, player_id: usize) -> f32 {
+        self.setup.respawn_time * self.get_handicap(player_id).respawn_time
+    }
+
+    // Team play only, the lobby's picks are kept and everyone else is balanced in
+    pub fn assign_teams(&mut self, team_requests: &[Option<usize>]) {
+        if self.setup.teams {
+            self.player_teams = balance_teams(team_requests);
+        }
+    }
+
+    // Per player values summed by team, indexed by team.
+    // Without team play everyone is their own team, so this is by player.
+    pub fn sum_by_team<T: AddAssign + Default + Copy>(&self, player_values: &[T]) -> Vec<T> {
+        let team_count = self.player_teams.iter().max().map_or(0, |team| team + 1);
+        let mut team_values = vec![T::default(); team_count];
+
+        for (player_id, value) in player_values.iter().enumerate() {
+            if let Some(team_value) = team_values.get_mut(self.get_team(player_id)) {
+                *team_value += *value;
+            }
+        }
+
+        team_values
+    }
+
+    // Player scores summed by team, indexed by team
+    pub fn team_scores<T: AddAssign + Default + Copy, F: Fn(&PlayerScore) -> T>(&self, score_fn: F) -> Vec<T> {
+        let player_values: Vec<T> = self.scores.iter().map(score_fn).collect();
+        self.sum_by_team(&player_values)
+    }
+
+    // A kill for the credited attacker, or a suicide when there is none
+    pub fn record_death(&mut self, player_id: usize, killer_id: Option<usize>, rammed: bool) {
+        if let Some(score) = self.scores.get_mut(player_id) {
+            score.deaths += 1;
+        }
+
+        let killer_id = match killer_id {
+            Some(killer_id) if killer_id != player_id => {
+                if let Some(score) = self.scores.get_mut(killer_id) {
+                    score.kills += 1;
+                }
+                Some(killer_id)
+            },
+            _ => {
+                if let Some(score) = self.scores.get_mut(player_id) {
+                    score.suicides += 1;
+                }
+                None
+            },
+        };
+
+        self.kill_feed.push(KillEvent {
+            victim: player_id,
+            killer: killer_id,
+            rammed,
+            time: self.round_time,
+        });
+    }
+
+    // The newest kills that are still shown on the HUD, oldest first
+    pub fn recent_kills(&self) -> Vec<KillEvent> {
+        let recent: Vec<KillEvent> = self.kill_feed
+            .iter()
+            .filter(|kill_event| self.round_time - kill_event.time < KILL_FEED_TIME)
+            .cloned()
+            .collect();
+
+        recent[recent.len().saturating_sub(KILL_FEED_LINES)..].to_vec()
+    }
+
+    // The ram tier comes after every weapon on the ladder
+    pub fn ram_tier(&self) -> usize {
+        self.setup.weapon_ladder.len()
+    }
+
+    // The player's gun game weapon, None on the ram tier
+    pub fn ladder_weapon(&self, player_id: usize) -> Option<WeaponNames> {
+        let tier = self.scores.get(player_id).map_or(0, |score| score.tier);
+        self.setup.weapon_ladder.get(tier).cloned()
+    }
+
+    // Gun game: a kill moves the killer up the ladder, and a kill on the ram tier wins the round.
+    // Getting rammed to death also knocks the victim down a tier.
+    pub fn climb_ladder(&mut self, kill_event: &KillEvent) {
+        let ram_tier = self.ram_tier();
+
+        if let Some(killer_id) = kill_event.killer {
+            let killer_tier = self.scores.get(killer_id).map_or(0, |score| score.tier);
+
+            if killer_tier >= ram_tier {
+                if kill_event.rammed {
+                    self.end_round(Some(self.get_team(killer_id)));
+                }
+            }
+            else if let Some(score) = self.scores.get_mut(killer_id) {
+                score.tier += 1;
+            }
+
+            if kill_event.rammed {
+                if let Some(score) = self.scores.get_mut(kill_event.victim) {
+                    score.tier = score.tier.saturating_sub(1);
+                }
+            }
+        }
+    }
+
+    pub fn time_left(&self) -> Option<f32> {
+        let time_limit = self.setup.time_limit? + self.overtime_added;
+        Some((time_limit - self.round_time).max(0.0))
+    }
+
+    pub fn in_overtime(&self) -> bool {
+        self.overtime_added > 0.0 || self.time_left() == Some(0.0)
+    }
+
+    // The mode's sudden death rule, once the round has gone to overtime
+    pub fn sudden_death(&self) -> SuddenDeathRules {
+        if self.round_active() && self.in_overtime() {
+            self.setup.sudden_death
+        }
+        else {
+            SuddenDeathRules::Off
+        }
+    }
+
+    // The fraction of the arena still safe from a shrinking sudden death, 1.0 when it isn't shrinking
+    pub fn safe_zone_scale(&self) -> f32 {
+        match self.sudden_death() {
+            SuddenDeathRules::ShrinkingArena(shrink_time) => {
+                let time_limit = self.setup.time_limit.unwrap_or(0.0);
+                let shrunk = ((self.round_time - time_limit) / shrink_time.max(0.001)).min(1.0).max(0.0);
+                SAFE_ZONE_MIN_SCALE + (1.0 - shrunk) * (1.0 - SAFE_ZONE_MIN_SCALE)
+            },
+            _ => 1.0,
+        }
+    }
+
+    // Ends the round once the time limit is up, with the single leader by the given scores winning.
+    // A tie for the lead is settled by the mode's overtime rule.
+    pub fn check_time_limit(&mut self, scores: &[i32]) {
+        if !self.round_active() || self.time_left() != Some(0.0) {
+            return;
+        }
+
+        let leaders = get_leaders(scores);
+        if leaders.len() == 1 {
+            self.end_round(Some(leaders[0]));
+            return;
+        }
+
+        match self.setup.overtime {
+            OvertimeRules::Draw => self.end_round(None),
+            OvertimeRules::SuddenDeath => {}, //checked again every frame until someone leads
+            OvertimeRules::Extra(extra_time) if self.overtime_added == 0.0 => {
+                self.overtime_added = extra_time;
+            },
+            OvertimeRules::Extra(_) => self.end_round(None),
+        }
+    }
+
+    pub fn end_round(&mut self, winner: Option<usize>) {
+        if !self.round_active() {
+            return;
+        }
+
+        if let Some(winner) = winner {
+            if let Some(wins) = self.round_wins.get_mut(winner) {
+                *wins += 1;
+            }
+        }
+
+        self.round_end_time = self.setup.round_end_freeze + self.setup.intermission;
+
+        let wins_needed = self.setup.rounds / 2 + 1;
+        let rounds_played = self.round + 1;
+
+        if rounds_played >= self.setup.rounds || self.round_wins.iter().any(|wins| *wins >= wins_needed) {
+            self.state = RoundState::MatchOver(self.match_leader());
+        }
+        else {
+            self.state = RoundState::RoundOver(winner);
+        }
+    }
+
+    pub fn next_round(&mut self) {
+        self.round += 1;
+        for score in self.scores.iter_mut() {
+            *score = PlayerScore::default();
+        }
+        self.kill_feed.clear();
+        self.round_time = 0.0;
+        self.round_end_time = 0.0;
+        self.overtime_added = 0.0;
+        self.start_countdown();
+    }
+
+    // Remembered for later rounds on the same arena, unless the mode rebuilds walls every round
+    pub fn destroy_wall(&mut self, arena_name: ArenaNames, index: usize) {
+        if !self.setup.rebuild_walls && !self.destroyed_walls.contains(&(arena_name, index)) {
+            self.destroyed_walls.push((arena_name, index));
+        }
+    }
+
+    pub fn get_destroyed_walls(&self, arena_name: &ArenaNames) -> Vec<usize> {
+        self.destroyed_walls
+            .iter()
+            .filter(|(destroyed_arena, _)| destroyed_arena == arena_name)
+            .map(|(_, index)| *index)
+            .collect()
+    }
+
+    // The player with the most round wins, None if nobody has won or it is tied
+    pub fn match_leader(&self) -> Option<usize> {
+        let most_wins = *self.round_wins.iter().max()?;
+        if most_wins == 0 || self.round_wins.iter().filter(|wins| **wins == most_wins).count() > 1 {
+            return None;
+        }
+        self.round_wins.iter().position(|wins| *wins == most_wins)
+    }
+}
+
+
+// Players keep the team they picked, everyone else joins the smallest team.
+// Nobody is left without opponents, if every pick is the same team the latest picks are moved over,
+// so uneven teams like 1v3 are only played when asked for.
+pub fn balance_teams(team_requests: &[Option<usize>]) -> Vec<usize> {
+    let mut teams = vec![0; team_requests.len()];
+    let mut team_sizes = vec![0; TEAM_COUNT];
+
+    for (player_id, team_request) in team_requests.iter().enumerate() {
+        if let Some(team) = team_request {
+            let team = (*team).min(TEAM_COUNT - 1);
+            teams[player_id] = team;
+            team_sizes[team] += 1;
+        }
+    }
+
+    for (player_id, team_request) in team_requests.iter().enumerate() {
+        if team_request.is_none() {
+            let team = smallest_team(&team_sizes);
+            teams[player_id] = team;
+            team_sizes[team] += 1;
+        }
+    }
+
+    for player_id in (0..teams.len()).rev() {
+        let empty_team = match team_sizes.iter().position(|size| *size == 0) {
+            Some(empty_team) => empty_team,
+            None => break,
+        };
+
+        if team_sizes[teams[player_id]] > 1 {
+            team_sizes[teams[player_id]] -= 1;
+            teams[player_id] = empty_team;
+            team_sizes[empty_team] += 1;
+        }
+    }
+
+    teams
+}
+
+fn smallest_team(team_sizes: &[usize]) -> usize {
+    let smallest = team_sizes.iter().min().cloned().unwrap_or(0);
+    team_sizes.iter().position(|size| *size == smallest).unwrap_or(0)
+}
+
+
+// Ids of the players tied for the highest score
+pub fn get_leaders(scores: &[i32]) -> Vec<usize> {
+    match scores.iter().max() {
+        Some(best) => scores.iter()
+            .enumerate()
+            .filter(|(_, score)| *score == best)
+            .map(|(player_id, _)| player_id)
+            .collect(),
+        None => vec![],
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::components::{Arena, ProceduralArenaProperties};
+
+    fn arena_store(arena_names: &[ArenaNames]) -> ArenaStoreResource {
+        ArenaStoreResource {
+            properties: arena_names.iter().map(|name| (*name, Arena::default())).collect(),
+            procedural: ProceduralArenaProperties::default(),
+        }
+    }
+
+    #[test]
+    fn arenas_rotate_by_round_skipping_undefined() {
+        let setup = GameModeSetup {
+            arenas: vec![ArenaNames::ChaosCombat, ArenaNames::StandardCombat, ArenaNames::OpenEmptyMap],
+            ..GameModeSetup::default()
+        };
+        let mut match_resource = MatchResource::new(GameModes::DeathmatchKills, setup, 2);
+        let store = arena_store(&[ArenaNames::StandardCombat, ArenaNames::OpenEmptyMap]);
+
+        assert_eq!(match_resource.pick_arena(&store), ArenaNames::StandardCombat);
+        match_resource.next_round();
+        assert_eq!(match_resource.pick_arena(&store), ArenaNames::StandardCombat);
+        match_resource.next_round();
+        assert_eq!(match_resource.pick_arena(&store), ArenaNames::OpenEmptyMap);
+        match_resource.next_round();
+        assert_eq!(match_resource.pick_arena(&store), ArenaNames::StandardCombat);
+    }
+
+    #[test]
+    fn best_of_three_ends_on_majority() {
+        let setup = GameModeSetup {
+            rounds: 3,
+            ..GameModeSetup::default()
+        };
+        let mut match_resource = MatchResource::new(GameModes::DeathmatchStock, setup, 2);
+
+        match_resource.end_round(Some(1));
+        assert_eq!(match_resource.state, RoundState::RoundOver(Some(1)));
+
+        match_resource.next_round();
+        match_resource.end_round(Some(1));
+        assert_eq!(match_resource.state, RoundState::MatchOver(Some(1)));
+
+        // ignored once the round is already over
+        match_resource.end_round(Some(0));
+        assert_eq!(match_resource.round_wins, vec![0, 2]);
+    }
+
+    #[test]
+    fn lives_run_out_with_deaths() {
+        let setup = GameModeSetup {
+            lives: Some(2),
+            ..GameModeSetup::default()
+        };
+        let mut match_resource = MatchResource::new(GameModes::DeathmatchStock, setup, 2);
+
+        assert_eq!(match_resource.lives_left(0), Some(2));
+        match_resource.get_score_mut(0).unwrap().deaths += 1;
+        assert_eq!(match_resource.lives_left(0), Some(1));
+        match_resource.get_score_mut(0).unwrap().deaths += 1;
+        assert_eq!(match_resource.lives_left(0), Some(0));
+
+        // lives come back with the next round
+        match_resource.next_round();
+        assert_eq!(match_resource.lives_left(0), Some(2));
+
+        let unlimited = MatchResource::new(GameModes::DeathmatchKills, GameModeSetup::default(), 2);
+        assert_eq!(unlimited.lives_left(0), None);
+    }
+
+    #[test]
+    fn deaths_without_an_attacker_cost_a_kill() {
+        let mut match_resource = MatchResource::new(GameModes::DeathmatchKills, GameModeSetup::default(), 2);
+
+        match_resource.record_death(1, Some(0), false);
+        match_resource.record_death(0, None, false);
+        match_resource.record_death(0, Some(0), false);
+
+        assert_eq!(match_resource.scores[0].kill_score(), -1);
+        assert_eq!(match_resource.scores[0].kd_score(), -1);
+        assert_eq!(match_resource.scores[1].kd_score(), -1);
+        assert_eq!(match_resource.scores[1].kill_score(), 0);
+    }
+
+    #[test]
+    fn overtime_settles_ties() {
+        let setup = GameModeSetup {
+            time_limit: Some(60.0),
+            overtime: OvertimeRules::Extra(30.0),
+            ..GameModeSetup::default()
+        };
+        let mut match_resource = MatchResource::new(GameModes::DeathmatchTimedKD, setup, 3);
+
+        match_resource.round_time = 60.0;
+        match_resource.check_time_limit(&[2, 2, 1]);
+        assert!(match_resource.round_active());
+        assert!(match_resource.in_overtime());
+        assert_eq!(match_resource.time_left(), Some(30.0));
+
+        match_resource.round_time = 90.0;
+        match_resource.check_time_limit(&[2, 2, 1]);
+        assert_eq!(match_resource.state, RoundState::MatchOver(None));
+
+        let setup = GameModeSetup {
+            time_limit: Some(60.0),
+            overtime: OvertimeRules::SuddenDeath,
+            ..GameModeSetup::default()
+        };
+        let mut match_resource = MatchResource::new(GameModes::DeathmatchTimedKD, setup, 3);
+
+        match_resource.round_time = 75.0;
+        match_resource.check_time_limit(&[2, 2, 1]);
+        assert!(match_resource.round_active());
+        match_resource.check_time_limit(&[2, 3, 1]);
+        assert_eq!(match_resource.state, RoundState::MatchOver(Some(1)));
+    }
+
+    #[test]
+    fn team_scores_sum_teammates() {
+        let setup = GameModeSetup {
+            teams: true,
+            ..GameModeSetup::default()
+        };
+        let mut match_resource = MatchResource::new(GameModes::CaptureTheFlag, setup, 4);
+        assert_eq!(match_resource.player_teams, vec![0, 1, 0, 1]);
+
+        match_resource.scores[0].score = 1.0;
+        match_resource.scores[2].score = 2.0;
+        match_resource.scores[3].score = 1.0;
+        assert_eq!(match_resource.team_scores(|score| score.score as i32), vec![3, 1]);
+
+        // without teams everyone scores alone
+        let match_resource = MatchResource::new(GameModes::DeathmatchKills, GameModeSetup::default(), 3);
+        assert_eq!(match_resource.team_scores(|score| score.kills as i32), vec![0, 0, 0]);
+    }
+
+    #[test]
+    fn teams_balance_around_picks() {
+        assert_eq!(balance_teams(&[None, None, None, None]), vec![0, 1, 0, 1]);
+
+        // picks are kept, the rest fill the smaller team
+        assert_eq!(balance_teams(&[Some(1), Some(1), None, None]), vec![1, 1, 0, 0]);
+        assert_eq!(balance_teams(&[Some(0), None, Some(1), Some(1)]), vec![0, 0, 1, 1]);
+
+        // uneven teams when picked
+        assert_eq!(balance_teams(&[Some(0), Some(1), Some(1), Some(1)]), vec![0, 1, 1, 1]);
+
+        // but never a team left empty
+        assert_eq!(balance_teams(&[Some(0), Some(0), Some(0), Some(0)]), vec![0, 0, 0, 1]);
+        assert_eq!(balance_teams(&[Some(1), Some(1), Some(1), None]), vec![1, 1, 1, 0]);
+    }
+
+    #[test]
+    fn countdown_holds_the_round() {
+        let setup = GameModeSetup {
+            start_countdown: 3.0,
+            ..GameModeSetup::default()
+        };
+        let mut match_resource = MatchResource::new(GameModes::Race, setup, 2);
+
+        assert!(match_resource.counting_down());
+        assert!(!match_resource.round_active());
+        match_resource.update_countdown(2.0);
+        assert!(match_resource.counting_down());
+        match_resource.update_countdown(1.5);
+        assert!(match_resource.round_active());
+
+        match_resource.end_round(Some(0));
+        match_resource.next_round();
+        assert!(match_resource.counting_down());
+        assert_eq!(match_resource.countdown, 3.0);
+    }
+
+    #[test]
+    fn round_end_freezes_then_shows_the_scoreboard() {
+        let setup = GameModeSetup {
+            rounds: 3,
+            round_end_freeze: 2.0,
+            intermission: 5.0,
+            ..GameModeSetup::default()
+        };
+        let mut match_resource = MatchResource::new(GameModes::DeathmatchStock, setup, 2);
+        assert!(!match_resource.frozen());
+
+        match_resource.end_round(Some(1));
+        assert!(match_resource.frozen());
+        assert!(!match_resource.in_intermission());
+
+        match_resource.update_round_end(2.5);
+        assert!(match_resource.in_intermission());
+        assert!(!match_resource.ready_for_next_round());
+
+        match_resource.update_round_end(5.0);
+        assert!(match_resource.ready_for_next_round());
+
+        match_resource.next_round();
+        assert!(!match_resource.round_over());
+    }
+
+    #[test]
+    fn arena_shrinks_in_sudden_death() {
+        let setup = GameModeSetup {
+            time_limit: Some(60.0),
+            overtime: OvertimeRules::SuddenDeath,
+            sudden_death: SuddenDeathRules::ShrinkingArena(20.0),
+            ..GameModeSetup::default()
+        };
+        let mut match_resource = MatchResource::new(GameModes::DeathmatchKills, setup, 2);
+
+        match_resource.round_time = 30.0;
+        assert_eq!(match_resource.sudden_death(), SuddenDeathRules::Off);
+        assert_eq!(match_resource.safe_zone_scale(), 1.0);
+
+        match_resource.round_time = 70.0;
+        match_resource.check_time_limit(&[1, 1]);
+        assert!((match_resource.safe_zone_scale() - (SAFE_ZONE_MIN_SCALE + 0.5 * (1.0 - SAFE_ZONE_MIN_SCALE))).abs() < 0.001);
+
+        match_resource.round_time = 200.0;
+        assert_eq!(match_resource.safe_zone_scale(), SAFE_ZONE_MIN_SCALE);
+    }
+
+    #[test]
+    fn gun_game_ladder_ends_on_a_ram_kill() {
+        let setup = GameModeSetup {
+            weapon_ladder: vec![WeaponNames::ProtonCannon, WeaponNames::RailGun],
+            ..GameModeSetup::default()
+        };
+        let mut match_resource = MatchResource::new(GameModes::ClassicGunGame, setup, 2);
+
+        match_resource.record_death(1, Some(0), false);
+        match_resource.record_death(1, Some(0), false);
+        for kill_event in match_resource.kill_feed.clone().iter() {
+            match_resource.climb_ladder(kill_event);
+        }
+        assert_eq!(match_resource.ladder_weapon(0), None);
+        assert_eq!(match_resource.ladder_weapon(1), Some(WeaponNames::ProtonCannon));
+
+        // a rammed player drops a tier, the rammer still climbs
+        match_resource.climb_ladder(&KillEvent { victim: 0, killer: Some(1), rammed: true, time: 0.0 });
+        assert_eq!(match_resource.ladder_weapon(0), Some(WeaponNames::RailGun));
+        assert_eq!(match_resource.ladder_weapon(1), Some(WeaponNames::RailGun));
+
+        // a shot still in flight doesn't count on the ram tier
+        match_resource.climb_ladder(&KillEvent { victim: 1, killer: Some(0), rammed: false, time: 0.0 });
+        match_resource.climb_ladder(&KillEvent { victim: 1, killer: Some(0), rammed: false, time: 0.0 });
+        assert!(match_resource.round_active());
+        match_resource.climb_ladder(&KillEvent { victim: 1, killer: Some(0), rammed: true, time: 0.0 });
+        assert_eq!(match_resource.state, RoundState::MatchOver(Some(0)));
+    }
+
+    #[test]
+    fn kill_feed_shows_only_recent_kills() {
+        let mut match_resource = MatchResource::new(GameModes::DeathmatchKills, GameModeSetup::default(), 2);
+
+        match_resource.record_death(1, Some(0), false);
+        match_resource.round_time = 2.0;
+        for _ in 0..KILL_FEED_LINES {
+            match_resource.record_death(0, None, false);
+        }
+        assert_eq!(match_resource.recent_kills().len(), KILL_FEED_LINES);
+        assert!(match_resource.recent_kills().iter().all(|kill_event| kill_event.killer.is_none()));
+
+        match_resource.round_time = 2.0 + KILL_FEED_TIME;
+        assert_eq!(match_resource.recent_kills(), vec![]);
+    }
+
+    #[test]
+    fn tied_match_has_no_winner() {
+        let setup = GameModeSetup {
+            rounds: 2,
+            ..GameModeSetup::default()
+        };
+        let mut match_resource = MatchResource::new(GameModes::DeathmatchStock, setup, 2);
+
+        match_resource.end_round(Some(0));
+        match_resource.next_round();
+        match_resource.end_round(Some(1));
+        assert_eq!(match_resource.state, RoundState::MatchOver(None));
+    }
+
+    #[test]
+    fn destroyed_walls_stay_down_only_without_rebuilds() {
+        let mut rebuilding = MatchResource::new(GameModes::DeathmatchStock, GameModeSetup::default(), 2);
+        rebuilding.destroy_wall(ArenaNames::LargeCombat, 4);
+        rebuilding.next_round();
+        assert_eq!(rebuilding.get_destroyed_walls(&ArenaNames::LargeCombat), Vec::<usize>::new());
+
+        let setup = GameModeSetup {
+            rebuild_walls: false,
+            ..GameModeSetup::default()
+        };
+        let mut keeping = MatchResource::new(GameModes::DeathmatchStock, setup, 2);
+        keeping.destroy_wall(ArenaNames::LargeCombat, 4);
+        keeping.destroy_wall(ArenaNames::LargeCombat, 4);
+        keeping.destroy_wall(ArenaNames::StandardCombat, 2);
+        keeping.next_round();
+        assert_eq!(keeping.get_destroyed_walls(&ArenaNames::LargeCombat), vec![4]);
+        assert_eq!(keeping.get_destroyed_walls(&ArenaNames::OpenEmptyMap), Vec::<usize>::new());
+    }
+}
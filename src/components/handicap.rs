@@ -0,0 +1,138 @@
+// Per-player multipliers to even out mixed-skill matches, each set separately in the lobby.
+// Above 1.0 on damage dealt, power budget and below 1.0 on damage taken, respawn time helps the player.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Handicap {
+    pub damage_dealt: f32,
+    pub damage_taken: f32,
+    pub power_budget: f32,
+    pub respawn_time: f32,
+}
+
+impl Default for Handicap {
+    fn default() -> Handicap {
+        Handicap {
+            damage_dealt: 1.0,
+            damage_taken: 1.0,
+            power_budget: 1.0,
+            respawn_time: 1.0,
+        }
+    }
+}
+
+// Values each multiplier steps through in the lobby
+pub const HANDICAP_STEPS: [f32; 5] = [0.5, 0.75, 1.0, 1.25, 1.5];
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum HandicapSettings {
+    DamageDealt,
+    DamageTaken,
+    PowerBudget,
+    RespawnTime,
+}
+
+impl HandicapSettings {
+    pub fn next(&self) -> HandicapSettings {
+        match self {
+            HandicapSettings::DamageDealt => HandicapSettings::DamageTaken,
+            HandicapSettings::DamageTaken => HandicapSettings::PowerBudget,
+            HandicapSettings::PowerBudget => HandicapSettings::RespawnTime,
+            HandicapSettings::RespawnTime => HandicapSettings::DamageDealt,
+        }
+    }
+
+    pub fn prev(&self) -> HandicapSettings {
+        match self {
+            HandicapSettings::DamageDealt => HandicapSettings::RespawnTime,
+            HandicapSettings::DamageTaken => HandicapSettings::DamageDealt,
+            HandicapSettings::PowerBudget => HandicapSettings::DamageTaken,
+            HandicapSettings::RespawnTime => HandicapSettings::PowerBudget,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            HandicapSettings::DamageDealt => "Dealt",
+            HandicapSettings::DamageTaken => "Taken",
+            HandicapSettings::PowerBudget => "Power",
+            HandicapSettings::RespawnTime => "Respawn",
+        }
+    }
+}
+
+pub const HANDICAP_SETTINGS: [HandicapSettings; 4] = [
+    HandicapSettings::DamageDealt,
+    HandicapSettings::DamageTaken,
+    HandicapSettings::PowerBudget,
+    HandicapSettings::RespawnTime,
+];
+
+impl Handicap {
+    pub fn is_even(&self) -> bool {
+        *self == Handicap::default()
+    }
+
+    pub fn get(&self, setting: HandicapSettings) -> f32 {
+        match setting {
+            HandicapSettings::DamageDealt => self.damage_dealt,
+            HandicapSettings::DamageTaken => self.damage_taken,
+            HandicapSettings::PowerBudget => self.power_budget,
+            HandicapSettings::RespawnTime => self.respawn_time,
+        }
+    }
+
+    // Moves one multiplier to the next step, wrapping around to the lowest
+    pub fn step(&mut self, setting: HandicapSettings) {
+        let value = self.get(setting);
+        let next = HANDICAP_STEPS
+            .iter()
+            .cloned()
+            .find(|step| *step > value + 0.001)
+            .unwrap_or(HANDICAP_STEPS[0]);
+
+        match setting {
+            HandicapSettings::DamageDealt => self.damage_dealt = next,
+            HandicapSettings::DamageTaken => self.damage_taken = next,
+            HandicapSettings::PowerBudget => self.power_budget = next,
+            HandicapSettings::RespawnTime => self.respawn_time = next,
+        }
+    }
+
+    // The multipliers that are not even, like "Dealt x1.25 Respawn x0.5"
+    pub fn describe(&self) -> String {
+        let changed: Vec<String> = HANDICAP_SETTINGS
+            .iter()
+            .filter(|setting| self.get(**setting) != 1.0)
+            .map(|setting| format!("{} x{}", setting.label(), self.get(*setting)))
+            .collect();
+
+        changed.join(" ")
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settings_step_separately_and_wrap() {
+        let mut handicap = Handicap::default();
+
+        handicap.step(HandicapSettings::DamageDealt);
+        handicap.step(HandicapSettings::RespawnTime);
+        handicap.step(HandicapSettings::RespawnTime);
+        handicap.step(HandicapSettings::RespawnTime);
+
+        assert_eq!(handicap, Handicap {damage_dealt: 1.25, respawn_time: 0.5, ..Handicap::default()});
+        assert_eq!(handicap.describe(), "Dealt x1.25 Respawn x0.5");
+        assert!(!handicap.is_even());
+
+        handicap.step(HandicapSettings::RespawnTime);
+        handicap.step(HandicapSettings::RespawnTime);
+        for _ in 0..HANDICAP_STEPS.len() - 1 {
+            handicap.step(HandicapSettings::DamageDealt);
+        }
+        assert!(handicap.is_even());
+        assert_eq!(handicap.describe(), "");
+    }
+}
@@ -0,0 +1,36 @@
+use amethyst::ecs::prelude::{Component, DenseVecStorage};
+
+#[derive(Debug, PartialEq)]
+pub struct Health {
+    pub value: f32,
+    pub max: f32,
+}
+
+impl Component for Health {
+    type Storage = DenseVecStorage<Self>;
+}
+
+impl Health {
+    pub fn new(max: f32) -> Health {
+        Health {
+            value: max,
+            max,
+        }
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.value > 0.0
+    }
+
+    pub fn damage(&mut self, amount: f32) {
+        self.value = (self.value - amount).max(0.0);
+    }
+
+    pub fn repair(&mut self, amount: f32) {
+        self.value = (self.value + amount).min(self.max);
+    }
+
+    pub fn reset(&mut self) {
+        self.value = self.max;
+    }
+}
@@ -1,18 +1,21 @@
-use amethyst::ecs::prelude::{Component, DenseVecStorage};
+use amethyst::{
+    ecs::prelude::{Component, DenseVecStorage},
+    core::Transform,
+};
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-use ncollide2d::na::{Vector2};
+use ncollide2d::na::{Isometry2, Vector2};
 use ncollide2d::shape::{Ball, Cuboid, ShapeHandle};
 
 
-#[derive(Copy, Clone, Debug, PartialEq, Deserialize)]
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum HitboxShape {
     Rectangle,
     Circle
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Deserialize)]
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct HitboxProperties {
     pub width: f32,
     pub height: f32,
@@ -47,4 +50,41 @@ impl Hitbox {
             props: HitboxProperties {width, height, shape},
             collider: Box::new(collider)}
     }
-}
\ No newline at end of file
+}
+
+
+// Position and shape of a fixed arena hitbox.
+// Rectangles stay axis aligned, their width and height are already swapped for rotation at spawn.
+pub fn get_fixed_shape_pos(
+    transform: &Transform,
+    hitbox: &Hitbox,
+) -> (Isometry2<f32>, ShapeHandle<f32>) {
+    let collider_pos = Isometry2::new(
+        Vector2::new(transform.translation().x, transform.translation().y), 0.0);
+
+    let collider_shape: ShapeHandle<f32> = match hitbox.props.shape {
+        HitboxShape::Circle => {
+            ShapeHandle::new(Ball::new(hitbox.props.width / 2.0))
+        },
+        HitboxShape::Rectangle => {
+            ShapeHandle::new(Cuboid::new(Vector2::new(hitbox.props.width / 2.0, hitbox.props.height / 2.0)))
+        }
+    };
+
+    (collider_pos, collider_shape)
+}
+
+// Position and shape of an arena hitbox that moves or turns with its transform.
+// The width and height were swapped for the rotation at spawn, so only the turn since then applies.
+pub fn get_moving_shape_pos(
+    transform: &Transform,
+    hitbox: &Hitbox,
+    spawn_rotation: f32,
+) -> (Isometry2<f32>, ShapeHandle<f32>) {
+    let (fixed_pos, collider_shape) = get_fixed_shape_pos(transform, hitbox);
+    let (_, _, angle) = transform.rotation().euler_angles();
+
+    let collider_pos = Isometry2::new(fixed_pos.translation.vector, angle - spawn_rotation.to_radians());
+
+    (collider_pos, collider_shape)
+}
@@ -0,0 +1,28 @@
+use amethyst::ecs::prelude::{Component, DenseVecStorage};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PlayerHud {
+    pub id: usize,
+}
+
+impl Component for PlayerHud {
+    type Storage = DenseVecStorage<Self>;
+}
+
+
+// The round clock, and the scoreboard between rounds
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub struct MatchHud;
+
+impl Component for MatchHud {
+    type Storage = DenseVecStorage<Self>;
+}
+
+
+// The latest kills of the round
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub struct KillFeedHud;
+
+impl Component for KillFeedHud {
+    type Storage = DenseVecStorage<Self>;
+}
@@ -0,0 +1,103 @@
+use crate::components::{GameModes, Handicap, HandicapSettings, ShipClasses, TEAM_COUNT};
+
+
+pub const MAX_PLAYERS: usize = 4;
+pub const MIN_PLAYERS: usize = 2; //joined players needed to start a match
+
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlayerLobbySettings {
+    pub ship_class: ShipClasses,
+    pub team: Option<usize>, //None to be balanced in automatically
+    pub handicap: Handicap,
+    pub handicap_setting: HandicapSettings, //the multiplier the trigger steps
+    pub joined: bool, //has pressed a button on their controller
+    pub ready: bool,
+}
+
+impl Default for PlayerLobbySettings {
+    fn default() -> PlayerLobbySettings {
+        PlayerLobbySettings {
+            ship_class: ShipClasses::Medium,
+            team: None,
+            handicap: Handicap::default(),
+            handicap_setting: HandicapSettings::DamageDealt,
+            joined: false,
+            ready: false,
+        }
+    }
+}
+
+// Choices made in the lobby, indexed by player id
+#[derive(Clone, Debug, PartialEq)]
+pub struct LobbyResource {
+    pub game_mode: GameModes,
+    pub teams: bool, //team play, defaults to the game mode's setting
+    pub players: Vec<PlayerLobbySettings>,
+}
+
+impl Default for LobbyResource {
+    fn default() -> LobbyResource {
+        LobbyResource {
+            game_mode: GameModes::DeathmatchKills,
+            teams: false,
+            players: vec![PlayerLobbySettings::default(); MAX_PLAYERS],
+        }
+    }
+}
+
+impl LobbyResource {
+    pub fn get_player(&self, player_id: usize) -> PlayerLobbySettings {
+        match self.players.get(player_id) {
+            Some(player_settings_get) => player_settings_get.clone(),
+            _ => PlayerLobbySettings::default(),
+        }
+    }
+
+    pub fn team_requests(&self) -> Vec<Option<usize>> {
+        self.players.iter().map(|player_settings| player_settings.team).collect()
+    }
+
+    pub fn handicaps(&self) -> Vec<Handicap> {
+        self.players.iter().map(|player_settings| player_settings.handicap).collect()
+    }
+
+    // Every joined player is ready, and enough have joined for a match.
+    // Slots without a controller never join, so they don't hold the match up.
+    pub fn can_start(&self) -> bool {
+        let joined: Vec<&PlayerLobbySettings> = self.players.iter().filter(|player_settings| player_settings.joined).collect();
+
+        joined.len() >= MIN_PLAYERS && joined.iter().all(|player_settings| player_settings.ready)
+    }
+}
+
+// Cycles a player's team pick through auto and each team
+pub fn next_team_request(team: Option<usize>) -> Option<usize> {
+    match team {
+        None => Some(0),
+        Some(team) if team + 1 < TEAM_COUNT => Some(team + 1),
+        Some(_) => None,
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn two_joined_players_can_start() {
+        let mut lobby = LobbyResource::default();
+        assert!(!lobby.can_start());
+
+        lobby.players[0].joined = true;
+        lobby.players[0].ready = true;
+        assert!(!lobby.can_start()); //one player is not a match
+
+        lobby.players[1].joined = true;
+        assert!(!lobby.can_start());
+
+        lobby.players[1].ready = true;
+        assert!(lobby.can_start());
+    }
+}
@@ -7,15 +7,56 @@ mod weapon;
 mod power;
 mod cooldown;
 mod arena;
+mod arena_validation;
+mod arena_generator;
 mod camera_ortho;
+mod health;
+mod pickup;
+mod game_mode;
+mod status_effect;
+mod power_preset;
+mod hud;
+mod ship;
+mod lobby;
+mod zone;
+mod flag;
+mod race;
+mod drone;
+mod safe_zone;
+mod handicap;
 
-pub use self::movable::{Movable, CollisionType, calc_bounce_angle, get_movable_shape_pos};
+pub use self::movable::{Movable, CollisionType, calc_bounce_angle, calc_moving_wall_bounce, get_movable_shape_pos};
 pub use self::mass::Mass;
-pub use self::player::{Player, PlayerState, AimControlState};
-pub use self::hitbox::{Hitbox, HitboxProperties, HitboxShape};
-pub use self::shield::{Shield, ShieldAimChild};
-pub use self::weapon::{Weapon, WeaponAimChild, WeaponFire};
-pub use self::power::{Powerable};
+pub use self::player::{Player, PlayerState, AimControlState, KILL_CREDIT_TIME};
+pub use self::hitbox::{Hitbox, HitboxProperties, HitboxShape, get_fixed_shape_pos, get_moving_shape_pos};
+pub use self::shield::{Shield, ShieldAimChild, calc_shield_coverage_deg, calc_shield_coverage_step};
+pub use self::weapon::{Weapon, WeaponAimChild, WeaponFire, WeaponNames, WeaponProperties, WeaponStoreResource};
+pub use self::power::{
+    Powerable, PowerBus, PowerBusProperties, PowerSubsystems, calc_hit_subsystem, get_subsystem_powers};
 pub use self::cooldown::{Cooldown};
-pub use self::arena::{ArenaStoreResource, Arena, ArenaNames, ArenaElement, ArenaElementKinds, ArenaFloor};
-pub use self::camera_ortho::{CameraOrthoEdges, CameraPlayerBounds};
\ No newline at end of file
+pub use self::arena::{
+    ArenaStoreResource, Arena, ArenaNames, ArenaCheckpoint, ArenaElement, ArenaElementHitbox, ArenaElementKinds,
+    ArenaElementMotion, ArenaFloor, Debris, DestructibleWall, FlagSpawnPoint, MovingElement, PickupSpawnPoint,
+    PlayerSpawnPoint, TeamSpawnZone, Teleporter, WALL_DAMAGE_STATES};
+pub use self::arena_validation::{validate_arena, validate_arenas};
+pub use self::arena_generator::{generate_arena, ProceduralArenaProperties};
+pub use self::camera_ortho::{CameraOrthoEdges, CameraPlayerBounds, CAMERA_BUFFER, CAMERA_BUFFER_RACE};
+pub use self::health::Health;
+pub use self::pickup::{
+    Pickup, PickupKinds, PickupBoosts, PickupModeProperties, PickupStoreResource,
+    REPAIR_AMOUNT, SHIELD_OVERCHARGE_TIME, SPEED_BOOST_TIME};
+pub use self::game_mode::{
+    GameModes, GameModeSetup, GameModeStoreResource, KillEvent, MatchResource, OvertimeRules, PlayerScore, RoundState,
+    SuddenDeathRules, GAME_MODES, TEAM_COUNT, balance_teams, get_leaders};
+pub use self::status_effect::{StatusEffects, StatusEffectKinds, StatusEffectProperties};
+pub use self::power_preset::{PowerPreset, PowerPresets, PlayerProfile, PlayerProfileResource};
+pub use self::hud::{KillFeedHud, MatchHud, PlayerHud};
+pub use self::ship::{ShipClasses, ShipProperties, ShipStoreResource};
+pub use self::lobby::{LobbyResource, PlayerLobbySettings, MAX_PLAYERS, next_team_request};
+pub use self::zone::{HillZone, ZoneStates};
+pub use self::flag::{Flag, FlagBase, FlagStates, FLAG_CARRIER_MASS, FLAG_RETURN_TIME};
+pub use self::race::{Checkpoint, CheckpointResult, RaceProgress};
+pub use self::drone::{
+    Drone, DroneKinds, DroneProperties, SurvivalProperties, SurvivalStoreResource, WaveProperties, WaveSpawn};
+pub use self::safe_zone::{SafeZoneEdge, SafeZoneSides, SAFE_ZONE_SIDES};
+pub use self::handicap::{Handicap, HandicapSettings, HANDICAP_SETTINGS};
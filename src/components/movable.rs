@@ -32,11 +32,11 @@ impl Component for Movable {
 }
 
 impl Movable {
-    pub fn new(power:u8, max_accel_force: f32, collision_type: CollisionType) -> Movable {
+    pub fn new(power: Powerable, max_accel_force: f32, collision_type: CollisionType) -> Movable {
         Movable {
             dx: 0.0,
             dy: 0.0,
-            power: Powerable::new(power, power),
+            power,
             max_accel_force,
             collision_type,
             prevent_collision_id: None,
@@ -113,6 +113,26 @@ pub fn calc_bounce_angle(
     }
 }
 
+// Velocity after bouncing off a wall that may itself be moving.
+// The normal points out of the wall, the wall velocity is the wall's at the contact point,
+// so a sweeping wall knocks the moving entity along with it.
+pub fn calc_moving_wall_bounce(
+    normal_x: f32,
+    normal_y: f32,
+    wall_dx: f32,
+    wall_dy: f32,
+    moving_dx: f32,
+    moving_dy: f32,
+) -> (f32, f32) {
+    let closing_speed = (moving_dx - wall_dx) * normal_x + (moving_dy - wall_dy) * normal_y;
+
+    if closing_speed >= 0.0 {
+        return (moving_dx, moving_dy); //already separating
+    }
+
+    (moving_dx - 2.0 * closing_speed * normal_x, moving_dy - 2.0 * closing_speed * normal_y)
+}
+
 #[cfg(test)]
 mod tests {
     // Note this useful idiom: importing names from outer (for mod tests) scope.
@@ -309,4 +329,51 @@ mod tests {
         assert_approx_eq!(moving_dx_new, 1.0);
         assert_approx_eq!(moving_dy_new, 0.0);
     }
+
+    // Moving Wall Bounce Testing
+
+    #[test]
+    fn test_calc_moving_wall_bounce_still_wall_reflects() {
+        let (moving_dx_new, moving_dy_new) = calc_moving_wall_bounce(
+            0.0,
+            1.0, //normal
+            0.0,
+            0.0, //wall dx, dy
+            1.0,
+            -2.0, //moving dx, dy
+        );
+
+        assert_approx_eq!(moving_dx_new, 1.0);
+        assert_approx_eq!(moving_dy_new, 2.0);
+    }
+
+    #[test]
+    fn test_calc_moving_wall_bounce_wall_catches_up_to_still_entity() {
+        let (moving_dx_new, moving_dy_new) = calc_moving_wall_bounce(
+            -1.0,
+            0.0, //normal
+            -3.0,
+            0.0, //wall dx, dy
+            0.0,
+            0.0, //moving dx, dy
+        );
+
+        assert_approx_eq!(moving_dx_new, -6.0);
+        assert_approx_eq!(moving_dy_new, 0.0);
+    }
+
+    #[test]
+    fn test_calc_moving_wall_bounce_outrunning_wall_keeps_velocity() {
+        let (moving_dx_new, moving_dy_new) = calc_moving_wall_bounce(
+            1.0,
+            0.0, //normal
+            2.0,
+            0.0, //wall dx, dy
+            5.0,
+            1.0, //moving dx, dy
+        );
+
+        assert_approx_eq!(moving_dx_new, 5.0);
+        assert_approx_eq!(moving_dy_new, 1.0);
+    }
 }
@@ -0,0 +1,131 @@
+use amethyst::ecs::prelude::{Component, DenseVecStorage};
+
+use serde::{Deserialize, Serialize};
+
+use std::collections::HashMap;
+
+use crate::components::{Cooldown, GameModes, WeaponNames};
+
+
+pub const REPAIR_AMOUNT: f32 = 50.0;
+pub const SHIELD_OVERCHARGE_TIME: f32 = 8.0;
+pub const SPEED_BOOST_TIME: f32 = 5.0;
+pub const SPEED_BOOST_MULT: f32 = 1.5;
+
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum PickupKinds {
+    Repair,
+    ShieldOvercharge,
+    WeaponSwap(WeaponNames),
+    SpeedBoost,
+}
+
+// Which pickups a game mode allows, and how fast they come back
+#[derive(Copy, Clone, Debug, PartialEq, Deserialize)]
+pub struct PickupModeProperties {
+    pub repair: bool,
+    pub shield_overcharge: bool,
+    pub weapon_swap: bool,
+    pub speed_boost: bool,
+    pub respawn_time_mult: f32,
+}
+
+impl Default for PickupModeProperties {
+    fn default() -> PickupModeProperties {
+        PickupModeProperties {
+            repair: true,
+            shield_overcharge: true,
+            weapon_swap: true,
+            speed_boost: true,
+            respawn_time_mult: 1.0,
+        }
+    }
+}
+
+impl PickupModeProperties {
+    pub fn kind_enabled(&self, kind: &PickupKinds) -> bool {
+        match kind {
+            PickupKinds::Repair => self.repair,
+            PickupKinds::ShieldOvercharge => self.shield_overcharge,
+            PickupKinds::WeaponSwap(_) => self.weapon_swap,
+            PickupKinds::SpeedBoost => self.speed_boost,
+        }
+    }
+}
+
+pub struct PickupStoreResource {
+    pub properties: HashMap<GameModes, PickupModeProperties>,
+}
+
+
+// One entity per arena pickup spawn point, hidden while waiting to respawn.
+// Multiple kinds on the same spawn point are cycled through on each respawn.
+#[derive(Debug, PartialEq)]
+pub struct Pickup {
+    kinds: Vec<PickupKinds>,
+    kind_index: usize,
+    pub available: bool,
+    pub respawn: Cooldown,
+}
+
+impl Component for Pickup {
+    type Storage = DenseVecStorage<Self>;
+}
+
+impl Pickup {
+    pub fn new(kinds: Vec<PickupKinds>, respawn_time: f32) -> Pickup {
+        Pickup {
+            kinds,
+            kind_index: 0,
+            available: true,
+            respawn: Cooldown::new(0.0, respawn_time),
+        }
+    }
+
+    pub fn kind(&self) -> PickupKinds {
+        self.kinds[self.kind_index]
+    }
+
+    pub fn collect(&mut self) -> PickupKinds {
+        let kind = self.kind();
+
+        self.available = false;
+        self.respawn.timer_reset();
+        self.kind_index = (self.kind_index + 1) % self.kinds.len();
+
+        kind
+    }
+}
+
+
+// Timed effects granted by pickups, counted down in seconds
+#[derive(Debug, PartialEq, Default)]
+pub struct PickupBoosts {
+    pub shield_overcharge: f32,
+    pub speed_boost: f32,
+}
+
+impl Component for PickupBoosts {
+    type Storage = DenseVecStorage<Self>;
+}
+
+impl PickupBoosts {
+    pub fn timer_update(&mut self, dt: &f32) {
+        self.shield_overcharge = (self.shield_overcharge - dt).max(0.0);
+        self.speed_boost = (self.speed_boost - dt).max(0.0);
+    }
+
+    pub fn shield_overcharge_active(&self) -> bool {
+        self.shield_overcharge > 0.0
+    }
+
+    pub fn speed_boost_mult(&self) -> f32 {
+        if self.speed_boost > 0.0 {
+            SPEED_BOOST_MULT
+        }
+        else {
+            1.0
+        }
+    }
+}
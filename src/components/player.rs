@@ -2,11 +2,16 @@ use amethyst::ecs::prelude::{Component, DenseVecStorage};
 
 use crate::components::{Cooldown};
 
+
+pub const KILL_CREDIT_TIME: f32 = 5.0; //seconds an attacker keeps credit for a kill after their last hit
+
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum PlayerState {
     Active,
     //InActive,
     InRespawn,
+    Eliminated, //out of lives, spectating until the round ends
+    Downed, //left where destroyed until a teammate revives them
 }
 
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -19,10 +24,17 @@ pub enum AimControlState {
 #[derive(Debug, PartialEq)]
 pub struct Player {
     pub id: usize,
+    pub team: usize,
     state: PlayerState,
     pub system_adjust_cooldown: Cooldown,
     aim_control_state: AimControlState,
     pub aim_mode_cooldown: Cooldown,
+    pub dash_cooldown: Cooldown,
+    pub dash_thrust_drain: Cooldown,
+    pub respawn_cooldown: Cooldown,
+    last_hit_by: Option<usize>,
+    last_hit_rammed: bool,
+    pub last_hit_cooldown: Cooldown,
 }
 
 impl Component for Player {
@@ -30,18 +42,32 @@ impl Component for Player {
 }
 
 impl Player {
-    pub fn new(id: usize, system_adjust_cooldown_reset: f32, aim_mode_cooldown_reset: f32) -> Player {
+    pub fn new(
+        id: usize,
+        team: usize,
+        system_adjust_cooldown_reset: f32,
+        aim_mode_cooldown_reset: f32,
+        dash_cooldown_reset: f32,
+        dash_thrust_drain_reset: f32,
+    ) -> Player {
         Player{ 
             id: id,
+            team: team,
             state: PlayerState::Active,
             system_adjust_cooldown: Cooldown::new(0.0, system_adjust_cooldown_reset),
             aim_control_state: AimControlState::Locked,
             aim_mode_cooldown: Cooldown::new(0.0, aim_mode_cooldown_reset),
+            dash_cooldown: Cooldown::new(0.0, dash_cooldown_reset),
+            dash_thrust_drain: Cooldown::new(0.0, dash_thrust_drain_reset),
+            respawn_cooldown: Cooldown::new(0.0, 0.0),
+            last_hit_by: None,
+            last_hit_rammed: false,
+            last_hit_cooldown: Cooldown::new(0.0, KILL_CREDIT_TIME),
         }
     }
 
     pub fn player_state_in_game(&self) -> bool {
-        if self.state == PlayerState::Active || self.state == PlayerState::InRespawn {
+        if self.state == PlayerState::Active || self.state == PlayerState::InRespawn || self.state == PlayerState::Downed {
             return true;
         }
         else {
@@ -49,6 +75,50 @@ impl Player {
         }
     }
 
+    pub fn is_active(&self) -> bool {
+        self.state == PlayerState::Active
+    }
+
+    pub fn get_state(&self) -> PlayerState {
+        self.state
+    }
+
+    pub fn set_state(&mut self, state: PlayerState) {
+        self.state = state;
+    }
+
+    pub fn hit_by(&mut self, attacker_id: usize) {
+        self.last_hit_by = Some(attacker_id);
+        self.last_hit_rammed = false;
+        self.last_hit_cooldown.timer_reset();
+    }
+
+    pub fn rammed_by(&mut self, attacker_id: usize) {
+        self.hit_by(attacker_id);
+        self.last_hit_rammed = true;
+    }
+
+    // Whether the credited kill was a ram rather than weapon fire
+    pub fn killed_by_ram(&self) -> bool {
+        self.kill_credit().is_some() && self.last_hit_rammed
+    }
+
+    // The last attacker, if they hit recently enough to be credited with a kill.
+    // A death without one is a self-destruct or an environment death.
+    pub fn kill_credit(&self) -> Option<usize> {
+        if self.last_hit_cooldown.timer_active() {
+            None
+        }
+        else {
+            self.last_hit_by
+        }
+    }
+
+    pub fn clear_hits(&mut self) {
+        self.last_hit_by = None;
+        self.last_hit_rammed = false;
+    }
+
     pub fn aim_control_weapon_active(&self) -> bool {
         if self.aim_control_state == AimControlState::Weapon || self.aim_control_state == AimControlState::Locked {
             return true;
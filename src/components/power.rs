@@ -1,25 +1,187 @@
+use amethyst::ecs::prelude::{Component, DenseVecStorage};
+
+use serde::Deserialize;
+
+use std::cmp::Ordering;
+use std::f32::consts::PI;
+use std::collections::HashMap;
+
+use crate::components::{Movable, Shield, Weapon};
+
+
+pub const OVERCHARGE_STRESS_LIMIT: f32 = 6.0;
+pub const OVERCHARGE_STRESS_DECAY_RATE: f32 = 1.0;
+pub const HIT_DAMAGE_PER_LEVEL: f32 = 20.0;
+
+
 #[derive(Debug, PartialEq)]
 pub struct Powerable {
     level: u8,
     level_base: u8,
-    level_increment: u8
+    level_increment: u8,
+    level_base_max: u8, //level_base restored on repair, lowered for the rest of the life by hits
+    level_base_nominal: u8, //level_base at spawn
+    level_full: u8, //level_base without a handicap, power percentage is relative to this
+    overcharge_headroom: u8, //levels above level_base that can be held without stress
+    stress: f32, //builds up while overcharged above level_base + overcharge_headroom
+    hit_damage: f32, //accumulated hit damage not yet turned into a lost level
+    disabled: bool, //temporarily unpowered without losing the allocated level
+    drained: u8, //levels spent on an ability, given back once it recovers
+    released: u8, //levels above a lowered level_base, for the bus to hand to the other subsystems
 }
 
 impl Powerable {
-    pub fn new(level: u8, level_base: u8) -> Powerable {
-        Powerable {level: level, level_base: level_base, level_increment: level_base/3}
+    pub fn new(level: u8, level_base: u8, level_increment: u8) -> Powerable {
+        Powerable {
+            level: level,
+            level_base: level_base,
+            level_increment: level_increment,
+            level_base_max: level_base,
+            level_base_nominal: level_base,
+            level_full: level_base,
+            overcharge_headroom: 0,
+            stress: 0.0,
+            hit_damage: 0.0,
+            disabled: false,
+            drained: 0,
+            released: 0,
+        }
     }
 
     pub fn is_powered(&self) -> bool {
-        self.level > 0
+        !self.disabled && self.level > 0
     }
 
     pub fn get_power_pct(&self) -> f32 {
-        (self.level as f32) / (self.level_base as f32)
+        if self.disabled || self.level_full == 0 {
+            return 0.0;
+        }
+        (self.level as f32) / (self.level_full as f32)
+    }
+
+    // A handicapped subsystem spawns above or below the level it is measured against
+    pub fn with_level_full(mut self, level_full: u8) -> Powerable {
+        self.level_full = level_full;
+        self
+    }
+
+    // How far above level_base the subsystem can be held before it starts to overcharge
+    pub fn with_overcharge_headroom(mut self, overcharge_headroom: u8) -> Powerable {
+        self.overcharge_headroom = overcharge_headroom;
+        self
+    }
+
+    pub fn get_level(&self) -> u8 {
+        self.level
+    }
+
+    pub fn get_level_base(&self) -> u8 {
+        self.level_base
+    }
+
+    pub fn get_level_base_nominal(&self) -> u8 {
+        self.level_base_nominal
+    }
+
+    pub fn is_damaged(&self) -> bool {
+        self.level_base < self.level_base_nominal
+    }
+
+    pub fn is_overcharged(&self) -> bool {
+        self.level > self.level_base.saturating_add(self.overcharge_headroom)
+    }
+
+    pub fn get_stress_pct(&self) -> f32 {
+        self.stress / OVERCHARGE_STRESS_LIMIT
+    }
+
+    // Stress builds at the power percentage per second while overcharged,
+    // and decays while not. Reaching the limit damages the subsystem by one level_base unit.
+    // Returns true if the subsystem was damaged.
+    pub fn update_stress(&mut self, dt: &f32) -> bool {
+        if self.is_overcharged() && !self.disabled {
+            self.stress += self.get_power_pct() * dt;
+        }
+        else {
+            self.stress = (self.stress - OVERCHARGE_STRESS_DECAY_RATE * dt).max(0.0);
+        }
+
+        if self.stress >= OVERCHARGE_STRESS_LIMIT {
+            self.stress -= OVERCHARGE_STRESS_LIMIT;
+            return self.damage(1);
+        }
+
+        false
+    }
+
+    // Lowers the level_base, never below 1. Any level above the new level_base is released
+    // for the PowerBus to hand to the other subsystems, so a damaged subsystem never stays
+    // overcharged and the ship's power is conserved.
+    pub fn damage(&mut self, amount: u8) -> bool {
+        let level_base = self.level_base.saturating_sub(amount).max(1);
+        let levels_lost = self.level_base - level_base;
+
+        self.level_base = level_base;
+        self.released += self.take(self.level.saturating_sub(level_base));
+
+        levels_lost > 0
+    }
+
+    // Hits lower the level_base one level per HIT_DAMAGE_PER_LEVEL of damage taken,
+    // and unlike overcharge damage this is not restored by repairs.
+    // Returns true if the subsystem lost a level.
+    pub fn hit(&mut self, damage: f32) -> bool {
+        self.hit_damage += damage;
+
+        let mut damaged = false;
+        while self.hit_damage >= HIT_DAMAGE_PER_LEVEL {
+            self.hit_damage -= HIT_DAMAGE_PER_LEVEL;
+            self.level_base_max = self.level_base_max.saturating_sub(1).max(1);
+            damaged |= self.damage(1);
+        }
+
+        damaged
+    }
+
+    pub fn repair(&mut self) {
+        self.level_base = self.level_base_max;
+        self.stress = 0.0;
+    }
+
+    // Full restore to the spawn state, undoing hits and overcharge damage.
+    pub fn restore(&mut self) {
+        self.level_base_max = self.level_base_nominal;
+        self.level_base = self.level_base_nominal;
+        self.level = self.level_base_nominal;
+        self.stress = 0.0;
+        self.hit_damage = 0.0;
+        self.disabled = false;
+        self.drained = 0;
+        self.released = 0;
+    }
+
+    pub fn set_disabled(&mut self, disabled: bool) {
+        self.disabled = disabled;
     }
 
     pub fn reset(&mut self) {
         self.level = self.level_base;
+        self.drained = 0;
+    }
+
+    // Spends levels on an ability until restore_drain gives them back.
+    // Returns the amount drained, never more than the current level.
+    pub fn drain(&mut self, amount: u8) -> u8 {
+        let drained = amount.min(self.level);
+        self.level -= drained;
+        self.drained += drained;
+
+        drained
+    }
+
+    pub fn restore_drain(&mut self) {
+        self.level += self.drained;
+        self.drained = 0;
     }
 
     pub fn down(&mut self) -> u8 {
@@ -45,4 +207,620 @@ impl Powerable {
         self.level += free_power;
     }
 
-}
\ No newline at end of file
+    // Levels released by damage since the last call
+    pub fn take_released(&mut self) -> u8 {
+        let released = self.released;
+        self.released = 0;
+
+        released
+    }
+
+    pub fn take(&mut self, amount: u8) -> u8 {
+        let power_freed = amount.min(self.level);
+        self.level -= power_freed;
+
+        power_freed
+    }
+
+}
+
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Deserialize)]
+pub enum PowerSubsystems {
+    Shield,
+    Thrust,
+    Weapon,
+}
+
+// Which subsystem a hit lands on, based on where it is relative to the ship heading and shield aim.
+// Hits inside the shield's coverage damage the shield, otherwise the front half is the weapon
+// and the rear half is the thrust. Angles are in radians, with 0 pointing up like the ship heading.
+pub fn calc_hit_subsystem(
+    player_x: f32,
+    player_y: f32,
+    player_angle: f32,
+    shield_angle: f32,
+    shield_coverage_deg: f32,
+    contact_x: f32,
+    contact_y: f32,
+) -> PowerSubsystems {
+    let offset_x = contact_x - player_x;
+    let offset_y = contact_y - player_y;
+
+    let contact_angle = (-offset_x).atan2(offset_y);
+    let mut shield_offset = (contact_angle - shield_angle) % (2.0 * PI);
+    if shield_offset > PI {
+        shield_offset -= 2.0 * PI;
+    }
+    else if shield_offset < -PI {
+        shield_offset += 2.0 * PI;
+    }
+
+    if shield_coverage_deg > 0.0 && shield_offset.abs().to_degrees() <= shield_coverage_deg / 2.0 {
+        return PowerSubsystems::Shield;
+    }
+
+    let forward_comp = offset_x * -player_angle.sin() + offset_y * player_angle.cos();
+
+    if forward_comp >= 0.0 {
+        PowerSubsystems::Weapon
+    }
+    else {
+        PowerSubsystems::Thrust
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct PowerBusProperties {
+    pub budget: u8,
+    pub step: u8,
+    pub subsystems: Vec<PowerSubsystems>,
+}
+
+impl Default for PowerBusProperties {
+    fn default() -> PowerBusProperties {
+        PowerBusProperties {
+            budget: 27,
+            step: 3,
+            subsystems: vec![PowerSubsystems::Shield, PowerSubsystems::Thrust, PowerSubsystems::Weapon],
+        }
+    }
+}
+
+
+// Owns the power budget of a ship. The Powerable levels stay on the subsystem components,
+// but all power movement between them goes through here so the total is always conserved.
+// Subsystems missing from the list get no power at all.
+#[derive(Debug, PartialEq)]
+pub struct PowerBus {
+    pub subsystems: Vec<PowerSubsystems>,
+    budget: u8,
+    budget_full: u8, //the ship's budget before any handicap
+    step: u8,
+}
+
+impl Component for PowerBus {
+    type Storage = DenseVecStorage<Self>;
+}
+
+impl PowerBus {
+    pub fn new(props: &PowerBusProperties) -> PowerBus {
+        let mut subsystems: Vec<PowerSubsystems> = Vec::new();
+        for subsystem in props.subsystems.iter() {
+            if !subsystems.contains(subsystem) {
+                subsystems.push(*subsystem);
+            }
+        }
+
+        // a ship needs somewhere to put its power, an empty list gets the default subsystems
+        if subsystems.is_empty() {
+            subsystems = PowerBusProperties::default().subsystems;
+        }
+
+        PowerBus {
+            subsystems,
+            budget: props.budget,
+            budget_full: props.budget,
+            step: props.step,
+        }
+    }
+
+    // Scales the budget for a player's handicap, keeping at least one level per subsystem
+    pub fn with_handicap(mut self, budget_scale: f32) -> PowerBus {
+        let min_budget = self.subsystems.len() as f32;
+        self.budget = (self.budget_full as f32 * budget_scale).round().max(min_budget).min(u8::MAX as f32) as u8;
+        self
+    }
+
+    // A single shift takes one step from every other subsystem, holding that is not an overcharge
+    pub fn overcharge_headroom(&self) -> u8 {
+        let others = self.subsystems.len().saturating_sub(1) as u8;
+        self.step.saturating_mul(others)
+    }
+
+    pub fn get_budget(&self) -> u8 {
+        self.budget
+    }
+
+    // The budget is split evenly, with any remainder going to the first subsystems in the list
+    pub fn base_level(&self, subsystem: &PowerSubsystems) -> u8 {
+        self.split_budget(self.budget, subsystem)
+    }
+
+    fn split_budget(&self, budget: u8, subsystem: &PowerSubsystems) -> u8 {
+        let count = self.subsystems.len() as u8;
+
+        match self.subsystems.iter().position(|s| s == subsystem) {
+            Some(index) if (index as u8) < budget % count => budget / count + 1,
+            Some(_) => budget / count,
+            None => 0,
+        }
+    }
+
+    pub fn create_powerable(&self, subsystem: &PowerSubsystems) -> Powerable {
+        let base = self.base_level(subsystem);
+        Powerable::new(base, base, self.step)
+            .with_level_full(self.split_budget(self.budget_full, subsystem))
+            .with_overcharge_headroom(self.overcharge_headroom())
+    }
+
+    pub fn total_level(&self, powers: &[&mut Powerable]) -> u16 {
+        powers.iter().map(|power| power.get_level() as u16).sum()
+    }
+
+    // Moves up to one step from every other subsystem into the target.
+    // Returns the amount of power moved.
+    pub fn shift_to(&self, powers: &mut [&mut Powerable], target: &PowerSubsystems) -> u8 {
+        let target_index = match self.subsystems.iter().position(|s| s == target) {
+            Some(index) if index < powers.len() => index,
+            _ => return 0,
+        };
+
+        let mut power_freed: u8 = 0;
+        for (index, power) in powers.iter_mut().enumerate() {
+            if index != target_index {
+                power_freed += power.down();
+            }
+        }
+
+        powers[target_index].up(power_freed);
+
+        power_freed
+    }
+
+    // Hands the levels released by damaged subsystems to the others, one level at a time
+    // to whichever has the least, earlier subsystems first on ties.
+    pub fn redistribute(&self, powers: &mut [&mut Powerable]) {
+        for index in 0..powers.len() {
+            let released = powers[index].take_released();
+
+            for _ in 0..released {
+                let target = (0..powers.len())
+                    .filter(|other| *other != index)
+                    .min_by_key(|other| powers[*other].get_level())
+                    .unwrap_or(index);
+
+                powers[target].up(1);
+            }
+        }
+    }
+
+    pub fn reset(&self, powers: &mut [&mut Powerable]) {
+        for power in powers.iter_mut() {
+            power.reset();
+        }
+    }
+
+    // Splits the current total between the subsystems by weight.
+    // Leftover units from rounding go to the largest remainders, earlier subsystems first on ties.
+    pub fn preset_levels(&self, powers: &[&mut Powerable], weights: &HashMap<PowerSubsystems, f32>) -> Vec<u8> {
+        let total = self.total_level(powers);
+        let subsystem_weights: Vec<f32> = self.subsystems
+            .iter()
+            .take(powers.len())
+            .map(|subsystem| weights.get(subsystem).cloned().unwrap_or(0.0).max(0.0))
+            .collect();
+        let weight_sum: f32 = subsystem_weights.iter().sum();
+
+        if weight_sum <= 0.0 {
+            return powers.iter().map(|power| power.get_level()).collect();
+        }
+
+        let raw_levels: Vec<f32> = subsystem_weights
+            .iter()
+            .map(|weight| total as f32 * weight / weight_sum)
+            .collect();
+        let mut levels: Vec<u8> = raw_levels.iter().map(|raw| raw.floor() as u8).collect();
+
+        let mut by_remainder: Vec<usize> = (0..levels.len()).collect();
+        by_remainder.sort_by(|a, b| {
+            let remainder_a = raw_levels[*a] - raw_levels[*a].floor();
+            let remainder_b = raw_levels[*b] - raw_levels[*b].floor();
+            remainder_b.partial_cmp(&remainder_a).unwrap_or(Ordering::Equal)
+        });
+
+        let assigned: u16 = levels.iter().map(|level| *level as u16).sum();
+        for index in by_remainder.iter().take((total - assigned) as usize) {
+            levels[*index] += 1;
+        }
+
+        levels
+    }
+
+    // Moves at most one step from the subsystem furthest above its target level
+    // to the one furthest below it. Returns true once every target is reached.
+    pub fn step_toward(&self, powers: &mut [&mut Powerable], target: &[u8]) -> bool {
+        let mut donor: Option<(usize, u8)> = None;
+        let mut receiver: Option<(usize, u8)> = None;
+
+        for (index, (power, target_level)) in powers.iter().zip(target.iter()).enumerate() {
+            let level = power.get_level();
+
+            if level > *target_level {
+                match donor {
+                    Some((_, surplus)) if surplus >= level - target_level => {},
+                    _ => donor = Some((index, level - target_level)),
+                }
+            }
+            else if level < *target_level {
+                match receiver {
+                    Some((_, deficit)) if deficit >= target_level - level => {},
+                    _ => receiver = Some((index, target_level - level)),
+                }
+            }
+        }
+
+        match (donor, receiver) {
+            (Some((donor_index, surplus)), Some((receiver_index, deficit))) => {
+                let amount = self.step.max(1).min(surplus).min(deficit);
+                let power_freed = powers[donor_index].take(amount);
+                powers[receiver_index].up(power_freed);
+
+                powers.iter().zip(target.iter()).all(|(power, target_level)| power.get_level() == *target_level)
+            },
+            _ => true,
+        }
+    }
+}
+
+
+// Collects the Powerables of a ship in the same order as its bus subsystems
+pub fn get_subsystem_powers<'a>(
+    bus: &PowerBus,
+    shield: &'a mut Shield,
+    movable: &'a mut Movable,
+    weapon: &'a mut Weapon,
+) -> Vec<&'a mut Powerable> {
+    let mut shield_power = Some(&mut shield.power);
+    let mut thrust_power = Some(&mut movable.power);
+    let mut weapon_power = Some(&mut weapon.power);
+
+    bus.subsystems
+        .iter()
+        .filter_map(|subsystem| match subsystem {
+            PowerSubsystems::Shield => shield_power.take(),
+            PowerSubsystems::Thrust => thrust_power.take(),
+            PowerSubsystems::Weapon => weapon_power.take(),
+        })
+        .collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bus(budget: u8, step: u8, subsystems: Vec<PowerSubsystems>) -> PowerBus {
+        PowerBus::new(&PowerBusProperties {budget, step, subsystems})
+    }
+
+    fn all_subsystems() -> Vec<PowerSubsystems> {
+        vec![PowerSubsystems::Shield, PowerSubsystems::Thrust, PowerSubsystems::Weapon]
+    }
+
+    #[test]
+    fn test_overcharge_stress_damages_base() {
+        let mut power = Powerable::new(18, 9, 3); //2x overcharge
+
+        assert!(!power.update_stress(&2.9));
+        assert!(power.update_stress(&0.2));
+        assert_eq!(power.get_level_base(), 8);
+        assert_eq!(power.get_level(), 8);
+        assert!(!power.is_overcharged());
+        assert!(power.is_damaged());
+
+        power.repair();
+        assert_eq!(power.get_level_base(), 9);
+        assert!(!power.is_damaged());
+    }
+
+    #[test]
+    fn test_no_stress_at_or_below_base() {
+        let mut power = Powerable::new(9, 9, 3);
+
+        for _ in 0..100 {
+            assert!(!power.update_stress(&1.0));
+        }
+        assert_eq!(power.get_stress_pct(), 0.0);
+    }
+
+    #[test]
+    fn test_stress_decays_when_not_overcharged() {
+        let mut power = Powerable::new(18, 9, 3);
+        power.update_stress(&2.0); //stress 4.0
+
+        power.take(9);
+        power.update_stress(&3.0);
+        assert_eq!(power.get_stress_pct(), 1.0 / OVERCHARGE_STRESS_LIMIT);
+    }
+
+    #[test]
+    fn test_damage_does_not_raise_power_pct() {
+        let mut power = Powerable::new(9, 9, 3);
+
+        assert!(power.damage(1));
+        assert_eq!(power.get_level(), 8);
+        assert!(power.get_power_pct() < 1.0);
+    }
+
+    #[test]
+    fn test_hit_damage_not_repaired() {
+        let mut power = Powerable::new(9, 9, 3);
+
+        assert!(!power.hit(HIT_DAMAGE_PER_LEVEL / 2.0));
+        assert!(power.hit(HIT_DAMAGE_PER_LEVEL / 2.0));
+        assert_eq!(power.get_level_base(), 8);
+
+        power.update_stress(&OVERCHARGE_STRESS_LIMIT); //8/9 is not overcharged
+        power.damage(1);
+        assert_eq!(power.get_level_base(), 7);
+
+        power.repair();
+        assert_eq!(power.get_level_base(), 8);
+        assert!(power.is_damaged());
+    }
+
+    #[test]
+    fn test_drain_is_given_back() {
+        let mut power = Powerable::new(9, 9, 3);
+
+        assert_eq!(power.drain(3), 3);
+        assert_eq!(power.get_level(), 6);
+        assert!(power.get_power_pct() < 1.0);
+
+        power.restore_drain();
+        assert_eq!(power.get_level(), 9);
+
+        // never below zero, and a reset forgets the drain
+        assert_eq!(Powerable::new(2, 9, 3).drain(3), 2);
+        power.drain(3);
+        power.reset();
+        power.restore_drain();
+        assert_eq!(power.get_level(), 9);
+    }
+
+    #[test]
+    fn test_calc_hit_subsystem() {
+        let right = -PI / 2.0;
+        let left = PI / 2.0;
+
+        // heading up, shield aimed right with 90 degrees of coverage
+        assert_eq!(calc_hit_subsystem(0.0, 0.0, 0.0, right, 90.0, 1.0, 0.2), PowerSubsystems::Shield);
+        assert_eq!(calc_hit_subsystem(0.0, 0.0, 0.0, right, 90.0, 0.0, 1.0), PowerSubsystems::Weapon);
+        assert_eq!(calc_hit_subsystem(0.0, 0.0, 0.0, right, 90.0, 0.0, -1.0), PowerSubsystems::Thrust);
+        assert_eq!(calc_hit_subsystem(0.0, 0.0, 0.0, right, 90.0, -1.0, 0.2), PowerSubsystems::Weapon);
+        assert_eq!(calc_hit_subsystem(0.0, 0.0, 0.0, right, 90.0, -1.0, -0.2), PowerSubsystems::Thrust);
+
+        // shield aimed backwards covers the rear instead of the thrust
+        assert_eq!(calc_hit_subsystem(0.0, 0.0, 0.0, PI, 90.0, 0.0, -1.0), PowerSubsystems::Shield);
+
+        // no shield coverage, nothing is shielded
+        assert_eq!(calc_hit_subsystem(0.0, 0.0, 0.0, right, 0.0, 1.0, 0.2), PowerSubsystems::Weapon);
+
+        // heading left, shield aimed left across the wrap around
+        assert_eq!(calc_hit_subsystem(5.0, 5.0, left, left, 60.0, 4.0, 5.1), PowerSubsystems::Shield);
+        assert_eq!(calc_hit_subsystem(5.0, 5.0, left, left - 2.0 * PI, 60.0, 4.0, 5.1), PowerSubsystems::Shield);
+        assert_eq!(calc_hit_subsystem(5.0, 5.0, left, right, 60.0, 4.0, 5.1), PowerSubsystems::Weapon);
+        assert_eq!(calc_hit_subsystem(5.0, 5.0, left, left, 60.0, 6.0, 5.1), PowerSubsystems::Thrust);
+    }
+
+    #[test]
+    fn test_single_shift_is_not_an_overcharge() {
+        let bus = bus(27, 3, all_subsystems());
+        let mut shield = bus.create_powerable(&PowerSubsystems::Shield);
+        let mut thrust = bus.create_powerable(&PowerSubsystems::Thrust);
+        let mut weapon = bus.create_powerable(&PowerSubsystems::Weapon);
+
+        bus.shift_to(&mut [&mut shield, &mut thrust, &mut weapon], &PowerSubsystems::Weapon);
+        assert_eq!(weapon.get_level(), 15);
+        assert!(!weapon.is_overcharged());
+        assert!(!weapon.update_stress(&60.0));
+
+        bus.shift_to(&mut [&mut shield, &mut thrust, &mut weapon], &PowerSubsystems::Weapon);
+        assert!(weapon.is_overcharged());
+    }
+
+    #[test]
+    fn test_overcharge_damage_stops_after_draining() {
+        let mut power = Powerable::new(27, 9, 3).with_overcharge_headroom(6);
+
+        let mut damage_count = 0;
+        for _ in 0..600 {
+            if power.update_stress(&0.1) {
+                damage_count += 1;
+            }
+        }
+
+        assert_eq!(damage_count, 1);
+        assert_eq!(power.get_level_base(), 8);
+        assert_eq!(power.get_level(), 8);
+    }
+
+    #[test]
+    fn test_damage_conserves_bus_power() {
+        let bus = bus(27, 3, all_subsystems());
+        let mut shield = bus.create_powerable(&PowerSubsystems::Shield);
+        let mut thrust = bus.create_powerable(&PowerSubsystems::Thrust);
+        let mut weapon = bus.create_powerable(&PowerSubsystems::Weapon);
+
+        bus.shift_to(&mut [&mut shield, &mut thrust, &mut weapon], &PowerSubsystems::Weapon);
+        bus.shift_to(&mut [&mut shield, &mut thrust, &mut weapon], &PowerSubsystems::Weapon);
+        assert_eq!(weapon.get_level(), 21);
+
+        assert!(weapon.damage(1));
+        let mut powers = [&mut shield, &mut thrust, &mut weapon];
+        bus.redistribute(&mut powers);
+
+        assert_eq!(bus.total_level(&powers), 27);
+        assert_eq!(powers[2].get_level(), 8);
+        assert_eq!(powers[0].get_level(), 10);
+        assert_eq!(powers[1].get_level(), 9);
+    }
+
+    #[test]
+    fn test_damage_keeps_minimum_base() {
+        let mut power = Powerable::new(3, 3, 1);
+
+        assert!(power.damage(5));
+        assert_eq!(power.get_level_base(), 1);
+        assert!(!power.damage(1));
+    }
+
+    #[test]
+    fn test_base_levels_sum_to_budget() {
+        for budget in 1..=60 {
+            for count in 1..=3 {
+                let bus = bus(budget, 2, all_subsystems()[..count].to_vec());
+                let total: u16 = bus.subsystems.iter().map(|s| bus.base_level(s) as u16).sum();
+
+                assert_eq!(total, budget as u16);
+            }
+        }
+    }
+
+    #[test]
+    fn test_handicap_scales_budget_not_full_power() {
+        let bus = bus(27, 3, all_subsystems()).with_handicap(0.7);
+        assert_eq!(bus.get_budget(), 19);
+
+        let powers: Vec<Powerable> = all_subsystems().iter().map(|s| bus.create_powerable(s)).collect();
+        assert_eq!(powers.iter().map(|power| power.get_level() as u16).sum::<u16>(), 19);
+        assert!((powers[0].get_power_pct() - 7.0 / 9.0).abs() < 0.001);
+
+        // never below one level per subsystem
+        assert_eq!(bus.with_handicap(0.0).get_budget(), 3);
+    }
+
+    #[test]
+    fn test_missing_subsystem_unpowered() {
+        let bus = bus(10, 2, vec![PowerSubsystems::Thrust, PowerSubsystems::Weapon]);
+        let shield = bus.create_powerable(&PowerSubsystems::Shield);
+
+        assert_eq!(bus.base_level(&PowerSubsystems::Shield), 0);
+        assert!(!shield.is_powered());
+        assert_eq!(shield.get_power_pct(), 0.0);
+    }
+
+    #[test]
+    fn test_empty_subsystems_fall_back_to_default() {
+        let bus = bus(10, 2, vec![]);
+
+        assert_eq!(bus.subsystems, all_subsystems());
+        let total: u16 = bus.subsystems.iter().map(|s| bus.base_level(s) as u16).sum();
+        assert_eq!(total, 10);
+    }
+
+    #[test]
+    fn test_duplicate_subsystems_ignored() {
+        let bus = bus(9, 3, vec![PowerSubsystems::Shield, PowerSubsystems::Shield, PowerSubsystems::Weapon]);
+
+        assert_eq!(bus.subsystems, vec![PowerSubsystems::Shield, PowerSubsystems::Weapon]);
+    }
+
+    #[test]
+    fn test_shift_moves_one_step_from_each() {
+        let bus = bus(27, 3, all_subsystems());
+        let mut shield = bus.create_powerable(&PowerSubsystems::Shield);
+        let mut thrust = bus.create_powerable(&PowerSubsystems::Thrust);
+        let mut weapon = bus.create_powerable(&PowerSubsystems::Weapon);
+        let mut powers = vec![&mut shield, &mut thrust, &mut weapon];
+
+        assert_eq!(bus.shift_to(&mut powers, &PowerSubsystems::Weapon), 6);
+        assert_eq!(powers[0].get_level(), 6);
+        assert_eq!(powers[1].get_level(), 6);
+        assert_eq!(powers[2].get_level(), 15);
+    }
+
+    #[test]
+    fn test_preset_levels_split_by_weight() {
+        let bus = bus(27, 3, all_subsystems());
+        let mut shield = bus.create_powerable(&PowerSubsystems::Shield);
+        let mut thrust = bus.create_powerable(&PowerSubsystems::Thrust);
+        let mut weapon = bus.create_powerable(&PowerSubsystems::Weapon);
+        let powers = vec![&mut shield, &mut thrust, &mut weapon];
+
+        let mut weights = HashMap::new();
+        weights.insert(PowerSubsystems::Weapon, 1.0);
+        assert_eq!(bus.preset_levels(&powers, &weights), vec![0, 0, 27]);
+
+        weights.insert(PowerSubsystems::Thrust, 1.0);
+        assert_eq!(bus.preset_levels(&powers, &weights), vec![0, 14, 13]);
+
+        weights.insert(PowerSubsystems::Shield, 2.0);
+        assert_eq!(bus.preset_levels(&powers, &weights), vec![13, 7, 7]);
+    }
+
+    #[test]
+    fn test_step_toward_reaches_preset_and_conserves() {
+        let bus = bus(27, 3, all_subsystems());
+        let mut shield = bus.create_powerable(&PowerSubsystems::Shield);
+        let mut thrust = bus.create_powerable(&PowerSubsystems::Thrust);
+        let mut weapon = bus.create_powerable(&PowerSubsystems::Weapon);
+        let mut powers = vec![&mut shield, &mut thrust, &mut weapon];
+
+        for target in [vec![0, 0, 27], vec![14, 7, 6], vec![1, 25, 1], vec![9, 9, 9]].iter() {
+            let mut steps = 0;
+            while !bus.step_toward(&mut powers, target) {
+                assert_eq!(bus.total_level(&powers), 27);
+                steps += 1;
+                assert!(steps < 100);
+            }
+
+            let levels: Vec<u8> = powers.iter().map(|power| power.get_level()).collect();
+            assert_eq!(&levels, target);
+            assert_eq!(bus.total_level(&powers), 27);
+        }
+    }
+
+    #[test]
+    fn test_total_always_conserved() {
+        // deterministic pseudo random sequence of shifts and resets over several bus setups
+        let mut seed: u32 = 12345;
+        let mut next = || {
+            seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+            (seed >> 16) as usize
+        };
+
+        for (budget, step, count) in [(27, 3, 3), (10, 3, 3), (20, 1, 2), (7, 4, 3), (1, 1, 3), (12, 5, 1)].iter() {
+            let subsystems = all_subsystems()[..*count].to_vec();
+            let bus = bus(*budget, *step, subsystems.clone());
+            let mut shield = bus.create_powerable(&PowerSubsystems::Shield);
+            let mut thrust = bus.create_powerable(&PowerSubsystems::Thrust);
+            let mut weapon = bus.create_powerable(&PowerSubsystems::Weapon);
+            let mut powers = vec![&mut shield, &mut thrust, &mut weapon];
+            powers.truncate(*count);
+
+            for _ in 0..1000 {
+                match next() % 5 {
+                    0 => bus.reset(&mut powers),
+                    n => {
+                        let target = all_subsystems()[n % 3];
+                        bus.shift_to(&mut powers, &target);
+                    }
+                }
+
+                assert_eq!(bus.total_level(&powers), *budget as u16);
+            }
+        }
+    }
+}
@@ -0,0 +1,198 @@
+use amethyst::ecs::prelude::{Component, DenseVecStorage};
+
+use serde::Deserialize;
+
+use std::collections::HashMap;
+
+use crate::components::{Cooldown, PowerSubsystems};
+
+
+pub const PRESET_HOLD_TIME: f32 = 0.5;
+pub const PRESET_TRANSITION_STEP_TIME: f32 = 0.05;
+
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct PowerPreset {
+    pub name: String,
+    pub weights: HashMap<PowerSubsystems, f32>,
+}
+
+impl PowerPreset {
+    fn new(name: &str, weights: &[(PowerSubsystems, f32)]) -> PowerPreset {
+        PowerPreset {
+            name: name.to_string(),
+            weights: weights.iter().cloned().collect(),
+        }
+    }
+}
+
+// Preset to jump to when holding each of the power adjust buttons
+#[derive(Clone, Debug, PartialEq, Deserialize, Default)]
+pub struct PowerQuickSelect {
+    #[serde(default)]
+    pub reset: Option<String>,
+    #[serde(default)]
+    pub shield: Option<String>,
+    #[serde(default)]
+    pub thrust: Option<String>,
+    #[serde(default)]
+    pub weapon: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct PlayerProfile {
+    pub name: String,
+    pub power_presets: Vec<PowerPreset>,
+    pub quick_select: PowerQuickSelect,
+}
+
+impl Default for PlayerProfile {
+    fn default() -> PlayerProfile {
+        PlayerProfile {
+            name: "Default".to_string(),
+            power_presets: vec![
+                PowerPreset::new("balanced", &[
+                    (PowerSubsystems::Shield, 1.0),
+                    (PowerSubsystems::Thrust, 1.0),
+                    (PowerSubsystems::Weapon, 1.0)]),
+                PowerPreset::new("all-shield", &[(PowerSubsystems::Shield, 1.0)]),
+                PowerPreset::new("all-weapon", &[(PowerSubsystems::Weapon, 1.0)]),
+                PowerPreset::new("runner", &[
+                    (PowerSubsystems::Shield, 1.0),
+                    (PowerSubsystems::Thrust, 2.0)]),
+            ],
+            quick_select: PowerQuickSelect {
+                reset: Some("balanced".to_string()),
+                shield: Some("all-shield".to_string()),
+                thrust: Some("runner".to_string()),
+                weapon: Some("all-weapon".to_string()),
+            },
+        }
+    }
+}
+
+pub struct PlayerProfileResource {
+    pub profiles: Vec<PlayerProfile>, //indexed by player id
+}
+
+impl PlayerProfileResource {
+    pub fn get_profile(&self, player_id: usize) -> PlayerProfile {
+        match self.profiles.get(player_id) {
+            Some(profile_get) => profile_get.clone(),
+            _ => PlayerProfile::default(),
+        }
+    }
+}
+
+
+// Per ship state for jumping to a power preset by long-pressing an adjust button.
+// Button order is: reset, shield, thrust, weapon
+#[derive(Debug, PartialEq)]
+pub struct PowerPresets {
+    presets: Vec<PowerPreset>,
+    quick_select: [Option<String>; 4],
+    hold_timers: [f32; 4],
+    hold_triggered: [bool; 4],
+    pub target: Option<Vec<u8>>,
+    pub transition_cooldown: Cooldown,
+}
+
+impl Component for PowerPresets {
+    type Storage = DenseVecStorage<Self>;
+}
+
+impl PowerPresets {
+    pub fn new(profile: &PlayerProfile) -> PowerPresets {
+        PowerPresets {
+            presets: profile.power_presets.clone(),
+            quick_select: [
+                profile.quick_select.reset.clone(),
+                profile.quick_select.shield.clone(),
+                profile.quick_select.thrust.clone(),
+                profile.quick_select.weapon.clone(),
+            ],
+            hold_timers: [0.0; 4],
+            hold_triggered: [false; 4],
+            target: None,
+            transition_cooldown: Cooldown::new(0.0, PRESET_TRANSITION_STEP_TIME),
+        }
+    }
+
+    pub fn get_preset(&self, name: &str) -> Option<&PowerPreset> {
+        self.presets.iter().find(|preset| preset.name == name)
+    }
+
+    // A button with a quick select preset only adjusts power once released before the hold time,
+    // holding it to the hold time selects the preset instead. Buttons without one adjust on press.
+    // Returns the buttons that adjust power this frame, and any preset selected by a hold.
+    pub fn update_holds(&mut self, dt: &f32, held: [bool; 4]) -> ([bool; 4], Option<PowerPreset>) {
+        let mut tapped = [false; 4];
+        let mut selected: Option<PowerPreset> = None;
+
+        for index in 0..4 {
+            let preset = match &self.quick_select[index] {
+                Some(name) => self.get_preset(name).cloned(),
+                None => None,
+            };
+
+            if held[index] {
+                if self.hold_timers[index] == 0.0 && preset.is_none() {
+                    tapped[index] = true;
+                }
+
+                self.hold_timers[index] += dt;
+
+                if self.hold_timers[index] >= PRESET_HOLD_TIME && !self.hold_triggered[index] {
+                    self.hold_triggered[index] = true;
+                    if preset.is_some() {
+                        selected = preset;
+                    }
+                }
+            }
+            else {
+                if self.hold_timers[index] > 0.0 && !self.hold_triggered[index] && preset.is_some() {
+                    tapped[index] = true;
+                }
+
+                self.hold_timers[index] = 0.0;
+                self.hold_triggered[index] = false;
+            }
+        }
+
+        (tapped, selected)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_press_adjusts_on_release_and_long_press_only_selects() {
+        let mut presets = PowerPresets::new(&PlayerProfile::default());
+
+        // held briefly, nothing happens until release
+        assert_eq!(presets.update_holds(&0.1, [false, true, false, false]), ([false; 4], None));
+        assert_eq!(presets.update_holds(&0.1, [false; 4]), ([false, true, false, false], None));
+
+        // held past the hold time selects the preset, and releasing does not adjust
+        assert_eq!(presets.update_holds(&0.3, [false, false, false, true]), ([false; 4], None));
+        let (tapped, selected) = presets.update_holds(&0.3, [false, false, false, true]);
+        assert_eq!(tapped, [false; 4]);
+        assert_eq!(selected.map(|preset| preset.name), Some("all-weapon".to_string()));
+        assert_eq!(presets.update_holds(&0.1, [false; 4]), ([false; 4], None));
+    }
+
+    #[test]
+    fn buttons_without_a_preset_adjust_on_press() {
+        let profile = PlayerProfile {
+            quick_select: PowerQuickSelect::default(),
+            ..PlayerProfile::default()
+        };
+        let mut presets = PowerPresets::new(&profile);
+
+        assert_eq!(presets.update_holds(&0.1, [true, false, false, false]), ([true, false, false, false], None));
+        assert_eq!(presets.update_holds(&0.6, [true, false, false, false]), ([false; 4], None));
+    }
+}
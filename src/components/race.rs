@@ -0,0 +1,148 @@
+use amethyst::ecs::prelude::{Component, DenseVecStorage};
+
+
+// A Race checkpoint, built from an arena checkpoint in course order.
+// Checkpoint 0 is the start/finish line.
+#[derive(Debug, PartialEq)]
+pub struct Checkpoint {
+    pub index: usize,
+}
+
+impl Component for Checkpoint {
+    type Storage = DenseVecStorage<Self>;
+}
+
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum CheckpointResult {
+    Passed,
+    LapComplete(f32), //lap time
+    Finished(f32), //final lap time
+    WrongWay,
+}
+
+// Each racer's place on the course. Racers start on the grid behind the start/finish line,
+// so checkpoint 0 counts as already passed and the first lap ends on crossing it again.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RaceProgress {
+    pub laps: u32, //completed
+    pub next_checkpoint: usize,
+    pub wrong_way: bool,
+    pub lap_times: Vec<f32>,
+    lap_start_time: f32,
+    pub finish_time: Option<f32>,
+}
+
+impl Component for RaceProgress {
+    type Storage = DenseVecStorage<Self>;
+}
+
+impl Default for RaceProgress {
+    fn default() -> RaceProgress {
+        RaceProgress {
+            laps: 0,
+            next_checkpoint: 1,
+            wrong_way: false,
+            lap_times: vec![],
+            lap_start_time: 0.0,
+            finish_time: None,
+        }
+    }
+}
+
+impl RaceProgress {
+    pub fn finished(&self) -> bool {
+        self.finish_time.is_some()
+    }
+
+    pub fn best_lap(&self) -> Option<f32> {
+        self.lap_times.iter().cloned().fold(None, |best, lap_time| match best {
+            Some(best) if best <= lap_time => Some(best),
+            _ => Some(lap_time),
+        })
+    }
+
+    // Checkpoints passed over the whole race, for ordering racers still on course
+    pub fn distance(&self, checkpoint_count: usize) -> usize {
+        let passed = (self.next_checkpoint + checkpoint_count - 1) % checkpoint_count;
+        self.laps as usize * checkpoint_count + passed
+    }
+
+    // Called while touching a checkpoint, race_time is seconds since the start.
+    // Touching the checkpoint just passed does nothing, any other out of order means going the wrong way.
+    pub fn touch_checkpoint(
+        &mut self,
+        index: usize,
+        checkpoint_count: usize,
+        lap_count: u32,
+        race_time: f32,
+    ) -> Option<CheckpointResult> {
+        if self.finished() || checkpoint_count == 0 {
+            return None;
+        }
+
+        let last_checkpoint = (self.next_checkpoint + checkpoint_count - 1) % checkpoint_count;
+
+        if index == self.next_checkpoint {
+            self.wrong_way = false;
+            self.next_checkpoint = (self.next_checkpoint + 1) % checkpoint_count;
+
+            if index != 0 {
+                return Some(CheckpointResult::Passed);
+            }
+
+            let lap_time = race_time - self.lap_start_time;
+            self.lap_start_time = race_time;
+            self.lap_times.push(lap_time);
+            self.laps += 1;
+
+            if self.laps >= lap_count {
+                self.finish_time = Some(race_time);
+                Some(CheckpointResult::Finished(lap_time))
+            }
+            else {
+                Some(CheckpointResult::LapComplete(lap_time))
+            }
+        }
+        else if index != last_checkpoint && !self.wrong_way {
+            self.wrong_way = true;
+            Some(CheckpointResult::WrongWay)
+        }
+        else {
+            None
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn laps_in_order_until_finished() {
+        let mut progress = RaceProgress::default();
+
+        // still on the start line
+        assert_eq!(progress.touch_checkpoint(0, 3, 2, 0.5), None);
+
+        assert_eq!(progress.touch_checkpoint(1, 3, 2, 4.0), Some(CheckpointResult::Passed));
+        assert_eq!(progress.touch_checkpoint(2, 3, 2, 8.0), Some(CheckpointResult::Passed));
+        assert_eq!(progress.touch_checkpoint(0, 3, 2, 12.0), Some(CheckpointResult::LapComplete(12.0)));
+        assert_eq!(progress.distance(3), 3);
+
+        // backtracking past checkpoint 2 is the wrong way, reported once
+        assert_eq!(progress.touch_checkpoint(2, 3, 2, 13.0), Some(CheckpointResult::WrongWay));
+        assert_eq!(progress.touch_checkpoint(2, 3, 2, 13.1), None);
+        assert!(progress.wrong_way);
+
+        assert_eq!(progress.touch_checkpoint(1, 3, 2, 16.0), Some(CheckpointResult::Passed));
+        assert!(!progress.wrong_way);
+        assert_eq!(progress.touch_checkpoint(2, 3, 2, 19.0), Some(CheckpointResult::Passed));
+        assert_eq!(progress.touch_checkpoint(0, 3, 2, 22.0), Some(CheckpointResult::Finished(10.0)));
+
+        assert_eq!(progress.finish_time, Some(22.0));
+        assert_eq!(progress.best_lap(), Some(10.0));
+        assert_eq!(progress.touch_checkpoint(1, 3, 2, 23.0), None);
+    }
+}
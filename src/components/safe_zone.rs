@@ -0,0 +1,27 @@
+use amethyst::ecs::prelude::{Component, DenseVecStorage};
+
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum SafeZoneSides {
+    Left,
+    Right,
+    Bottom,
+    Top,
+}
+
+pub const SAFE_ZONE_SIDES: [SafeZoneSides; 4] = [
+    SafeZoneSides::Left,
+    SafeZoneSides::Right,
+    SafeZoneSides::Bottom,
+    SafeZoneSides::Top,
+];
+
+// One side of the shrinking arena's safe area, hidden until sudden death starts
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SafeZoneEdge {
+    pub side: SafeZoneSides,
+}
+
+impl Component for SafeZoneEdge {
+    type Storage = DenseVecStorage<Self>;
+}
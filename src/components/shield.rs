@@ -26,15 +26,27 @@ impl Component for ShieldAimChild {
 }
 
 
-pub fn calc_shield_block_angle(
-    player_x: f32,
-    player_y: f32,
-    contact_x: f32,
-    contact_y: f32,
-    shield_angle: f32,
-    shield_coverage_deg: f32,
-) -> bool {
-    true
+// Power percentage needed for each step of shield coverage, in degrees around the aim.
+// The shield sprites follow the same steps, so what is drawn is what blocks.
+pub const SHIELD_COVERAGE_STEPS: [(f32, f32); 7] = [
+    (0.0, 0.0),
+    (0.333, 30.0),
+    (0.666, 60.0),
+    (1.0, 90.0),
+    (1.666, 180.0),
+    (2.333, 270.0),
+    (3.0, 360.0),
+];
+
+pub fn calc_shield_coverage_step(power_pct: f32) -> usize {
+    SHIELD_COVERAGE_STEPS
+        .iter()
+        .rposition(|(min_power_pct, _)| power_pct >= *min_power_pct)
+        .unwrap_or(0)
+}
+
+pub fn calc_shield_coverage_deg(power_pct: f32) -> f32 {
+    SHIELD_COVERAGE_STEPS[calc_shield_coverage_step(power_pct)].1
 }
 
 
@@ -42,14 +54,13 @@ pub fn calc_shield_block_angle(
 mod tests {
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     use super::*;
-    use assert_approx_eq::assert_approx_eq;
 
     #[test]
-    fn test_calc_shield_block_angle() {
-        assert!(calc_shield_block_angle(
-            0.0, 0.0,
-            1.0, 0.0,
-            0.0, 30.0,
-        ) == true);
+    fn test_calc_shield_coverage() {
+        assert_eq!(calc_shield_coverage_deg(0.0), 0.0);
+        assert_eq!(calc_shield_coverage_deg(0.5), 30.0);
+        assert_eq!(calc_shield_coverage_deg(1.0), 90.0);
+        assert_eq!(calc_shield_coverage_deg(2.9), 270.0);
+        assert_eq!(calc_shield_coverage_step(3.0), SHIELD_COVERAGE_STEPS.len() - 1);
     }
 }
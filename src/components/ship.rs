@@ -0,0 +1,71 @@
+use serde::Deserialize;
+
+use std::collections::HashMap;
+
+use crate::components::{PowerBusProperties, WeaponNames};
+
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Deserialize)]
+pub enum ShipClasses {
+    Light,
+    Medium,
+    Heavy,
+}
+
+impl ShipClasses {
+    pub fn next(&self) -> ShipClasses {
+        match self {
+            ShipClasses::Light => ShipClasses::Medium,
+            ShipClasses::Medium => ShipClasses::Heavy,
+            ShipClasses::Heavy => ShipClasses::Light,
+        }
+    }
+
+    pub fn prev(&self) -> ShipClasses {
+        match self {
+            ShipClasses::Light => ShipClasses::Heavy,
+            ShipClasses::Medium => ShipClasses::Light,
+            ShipClasses::Heavy => ShipClasses::Medium,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct ShipProperties {
+    pub mass: f32,
+    pub max_accel_force: f32,
+    pub health: f32,
+    pub hitbox_diameter: f32,
+    pub sprite_scale: f32, //player body sprites are 16x16
+    pub sprite_index: usize, //body sprite, white to be tinted by player or team
+    pub weapon: WeaponNames,
+    pub power: PowerBusProperties,
+}
+
+impl Default for ShipProperties {
+    fn default() -> ShipProperties {
+        ShipProperties {
+            mass: 1.0,
+            max_accel_force: 300.0,
+            health: 100.0,
+            hitbox_diameter: 8.0,
+            sprite_scale: 0.5,
+            sprite_index: 18,
+            weapon: WeaponNames::ProtonCannon,
+            power: PowerBusProperties::default(),
+        }
+    }
+}
+
+pub struct ShipStoreResource {
+    pub properties: HashMap<ShipClasses, ShipProperties>,
+}
+
+impl ShipStoreResource {
+    pub fn get_properties(&self, ship_class: &ShipClasses) -> ShipProperties {
+        match self.properties.get(ship_class) {
+            Some(ship_props_get) => ship_props_get.clone(),
+            _ => ShipProperties::default(),
+        }
+    }
+}
@@ -0,0 +1,212 @@
+use amethyst::ecs::prelude::{Component, DenseVecStorage};
+
+use serde::Deserialize;
+
+use std::cmp::Ordering;
+
+
+pub const MAX_STATUS_EFFECT_STACKS: usize = 3;
+pub const MIN_SLOW_MULT: f32 = 0.2;
+
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Deserialize)]
+pub enum StatusEffectKinds {
+    Emp, //all subsystems unpowered
+    Slow, //magnitude scales max_accel_force
+    Burn, //magnitude is damage per second
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Deserialize)]
+pub struct StatusEffectProperties {
+    pub kind: StatusEffectKinds,
+    pub duration: f32,
+    pub magnitude: f32,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct StatusEffect {
+    pub props: StatusEffectProperties,
+    pub remaining: f32,
+    pub owner: Option<usize>, //player id credited for kills by this effect
+}
+
+
+// Each application is its own stack with its own timer.
+// Stacks are kept in application order, and once a kind reaches MAX_STATUS_EFFECT_STACKS
+// the stack closest to expiring is refreshed instead of adding a new one.
+#[derive(Debug, PartialEq, Default)]
+pub struct StatusEffects {
+    effects: Vec<StatusEffect>,
+}
+
+impl Component for StatusEffects {
+    type Storage = DenseVecStorage<Self>;
+}
+
+impl StatusEffects {
+    pub fn apply(&mut self, props: StatusEffectProperties, owner: Option<usize>) {
+        let stacks = self.effects
+            .iter()
+            .filter(|effect| effect.props.kind == props.kind)
+            .count();
+
+        if stacks < MAX_STATUS_EFFECT_STACKS {
+            self.effects.push(StatusEffect {props, remaining: props.duration, owner});
+        }
+        else {
+            let oldest_index = self.effects
+                .iter()
+                .enumerate()
+                .filter(|(_, effect)| effect.props.kind == props.kind)
+                .min_by(|(_, a), (_, b)| a.remaining.partial_cmp(&b.remaining).unwrap_or(Ordering::Equal))
+                .map(|(index, _)| index);
+
+            if let Some(index) = oldest_index {
+                self.effects[index] = StatusEffect {props, remaining: props.duration, owner};
+            }
+        }
+    }
+
+    // Counts down all stacks and returns the burn damage dealt during this update.
+    // Burn only counts the part of dt it was still active for, so the total damage
+    // does not depend on the frame rate.
+    pub fn timer_update(&mut self, dt: &f32) -> f32 {
+        let mut burn_damage = 0.0;
+
+        for effect in self.effects.iter_mut() {
+            if effect.props.kind == StatusEffectKinds::Burn {
+                burn_damage += effect.props.magnitude * effect.remaining.min(*dt);
+            }
+            effect.remaining -= dt;
+        }
+
+        self.effects.retain(|effect| effect.remaining > 0.0);
+
+        burn_damage
+    }
+
+    // Owner of the burn stack that lasts the longest, to keep kill credit while it deals damage
+    pub fn burn_owner(&self) -> Option<usize> {
+        self.effects
+            .iter()
+            .filter(|effect| effect.props.kind == StatusEffectKinds::Burn && effect.owner.is_some())
+            .max_by(|a, b| a.remaining.partial_cmp(&b.remaining).unwrap_or(Ordering::Equal))
+            .and_then(|effect| effect.owner)
+    }
+
+    pub fn emp_active(&self) -> bool {
+        self.effects
+            .iter()
+            .any(|effect| effect.props.kind == StatusEffectKinds::Emp)
+    }
+
+    pub fn slow_mult(&self) -> f32 {
+        self.effects
+            .iter()
+            .filter(|effect| effect.props.kind == StatusEffectKinds::Slow)
+            .fold(1.0, |mult, effect| mult * effect.props.magnitude)
+            .max(MIN_SLOW_MULT)
+    }
+
+    pub fn clear(&mut self) {
+        self.effects.clear();
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_approx_eq::assert_approx_eq;
+
+    fn burn(duration: f32, dps: f32) -> StatusEffectProperties {
+        StatusEffectProperties {kind: StatusEffectKinds::Burn, duration, magnitude: dps}
+    }
+
+    fn slow(duration: f32, mult: f32) -> StatusEffectProperties {
+        StatusEffectProperties {kind: StatusEffectKinds::Slow, duration, magnitude: mult}
+    }
+
+    #[test]
+    fn test_burn_total_damage_independent_of_dt() {
+        let mut effects_fast = StatusEffects::default();
+        let mut effects_slow = StatusEffects::default();
+        effects_fast.apply(burn(1.0, 10.0), None);
+        effects_slow.apply(burn(1.0, 10.0), None);
+
+        let mut damage_fast = 0.0;
+        for _ in 0..100 {
+            damage_fast += effects_fast.timer_update(&(1.0 / 60.0));
+        }
+
+        let mut damage_slow = 0.0;
+        for _ in 0..10 {
+            damage_slow += effects_slow.timer_update(&0.3);
+        }
+
+        assert_approx_eq!(damage_fast, 10.0, 0.001);
+        assert_approx_eq!(damage_slow, 10.0, 0.001);
+    }
+
+    #[test]
+    fn test_burn_stacks_add() {
+        let mut effects = StatusEffects::default();
+        effects.apply(burn(1.0, 10.0), None);
+        effects.apply(burn(1.0, 5.0), None);
+
+        assert_approx_eq!(effects.timer_update(&0.5), 7.5);
+    }
+
+    #[test]
+    fn test_burn_owner_outlasts_shorter_stacks() {
+        let mut effects = StatusEffects::default();
+        assert_eq!(effects.burn_owner(), None);
+
+        effects.apply(burn(2.0, 10.0), Some(1));
+        effects.apply(burn(1.0, 10.0), Some(2));
+        effects.apply(slow(3.0, 0.5), Some(3));
+        assert_eq!(effects.burn_owner(), Some(1));
+
+        effects.timer_update(&2.0);
+        assert_eq!(effects.burn_owner(), None);
+    }
+
+    #[test]
+    fn test_stacks_capped_and_oldest_refreshed() {
+        let mut effects = StatusEffects::default();
+        effects.apply(slow(1.0, 0.9), None);
+        effects.timer_update(&0.5);
+        effects.apply(slow(2.0, 0.9), None);
+        effects.apply(slow(2.0, 0.9), None);
+        effects.apply(slow(2.0, 0.5), None); //replaces the first, closest to expiring
+
+        assert_approx_eq!(effects.slow_mult(), 0.9 * 0.9 * 0.5);
+
+        effects.timer_update(&1.0);
+        assert_approx_eq!(effects.slow_mult(), 0.9 * 0.9 * 0.5);
+
+        effects.timer_update(&1.0);
+        assert_approx_eq!(effects.slow_mult(), 1.0);
+    }
+
+    #[test]
+    fn test_slow_mult_clamped() {
+        let mut effects = StatusEffects::default();
+        effects.apply(slow(1.0, 0.1), None);
+        effects.apply(slow(1.0, 0.1), None);
+
+        assert_approx_eq!(effects.slow_mult(), MIN_SLOW_MULT);
+    }
+
+    #[test]
+    fn test_emp_expires() {
+        let mut effects = StatusEffects::default();
+        effects.apply(StatusEffectProperties {kind: StatusEffectKinds::Emp, duration: 0.5, magnitude: 0.0}, None);
+
+        assert!(effects.emp_active());
+        effects.timer_update(&0.25);
+        assert!(effects.emp_active());
+        effects.timer_update(&0.25);
+        assert!(!effects.emp_active());
+    }
+}
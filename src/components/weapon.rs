@@ -1,21 +1,99 @@
 use amethyst::ecs::prelude::{Component, DenseVecStorage};
 
-use crate::components::{Powerable, Cooldown};
+use serde::{Deserialize, Serialize};
+
+use std::collections::HashMap;
+
+use crate::components::{Powerable, Cooldown, StatusEffectProperties};
+
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum WeaponNames {
+    ProtonCannon,
+    PlasmaRepeater,
+    HeavyCannon,
+    RailGun,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Deserialize)]
+pub struct WeaponProperties {
+    pub shot_speed: f32,
+    pub damage: f32,
+    pub cooldown_reset: f32,
+    pub bounces: i8,
+    #[serde(default)]
+    pub status_effect: Option<StatusEffectProperties>, //applied to the player hit
+}
+
+impl Default for WeaponProperties {
+    fn default() -> WeaponProperties {
+        WeaponProperties {
+            shot_speed: 300.0,
+            damage: 10.0,
+            cooldown_reset: 0.333,
+            bounces: 2,
+            status_effect: None,
+        }
+    }
+}
+
+pub struct WeaponStoreResource {
+    pub properties: HashMap<WeaponNames, WeaponProperties>,
+}
+
+impl WeaponStoreResource {
+    pub fn get_properties(&self, name: &WeaponNames) -> WeaponProperties {
+        match self.properties.get(name) {
+            Some(weapon_props_get) => *weapon_props_get,
+            _ => WeaponProperties::default(),
+        }
+    }
+}
 
 
 #[derive(Debug, PartialEq)]
 pub struct Weapon {
+    pub name: WeaponNames,
     pub cooldown: Cooldown,
     pub power: Powerable,
     pub shot_speed: f32,
     pub damage: f32,
+    pub bounces: i8,
+    pub status_effect: Option<StatusEffectProperties>,
     pub angle: f32, //needs to be synchronized with child entity's weapon angle
+    pub holstered: bool, //can't fire, e.g. on the gun game ram tier
 }
 
 impl Component for Weapon {
     type Storage = DenseVecStorage<Self>;
 }
 
+impl Weapon {
+    pub fn new(name: WeaponNames, properties: &WeaponProperties, power: Powerable, angle: f32) -> Weapon {
+        Weapon {
+            name,
+            cooldown: Cooldown::new(0.0, properties.cooldown_reset),
+            power,
+            shot_speed: properties.shot_speed,
+            damage: properties.damage,
+            bounces: properties.bounces,
+            status_effect: properties.status_effect,
+            angle,
+            holstered: false,
+        }
+    }
+
+    // Swaps the firing properties only, power allocation and aim are kept
+    pub fn swap(&mut self, name: WeaponNames, properties: &WeaponProperties) {
+        self.name = name;
+        self.cooldown = Cooldown::new(0.0, properties.cooldown_reset);
+        self.shot_speed = properties.shot_speed;
+        self.damage = properties.damage;
+        self.bounces = properties.bounces;
+        self.status_effect = properties.status_effect;
+    }
+}
+
 
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct WeaponAimChild {
@@ -30,9 +108,11 @@ impl Component for WeaponAimChild {
 
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct WeaponFire {
-
+    pub owner_id: Option<usize>, //None for shots not fired by a player, like drones
+    pub damage: f32,
+    pub status_effect: Option<StatusEffectProperties>,
 }
 
 impl Component for WeaponFire {
     type Storage = DenseVecStorage<Self>;
-}
\ No newline at end of file
+}
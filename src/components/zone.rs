@@ -0,0 +1,122 @@
+use amethyst::ecs::prelude::{Component, DenseVecStorage};
+
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ZoneStates {
+    Neutral,
+    Capturing(usize), //team index
+    Captured(usize), //team index
+    Contested,
+}
+
+// A King of the Hill zone, built from an arena Zone element.
+// Only the active zone can be captured, the mode rotates which one that is.
+#[derive(Debug, PartialEq)]
+pub struct HillZone {
+    pub index: usize,
+    pub active: bool,
+    state: ZoneStates,
+    controller: Option<usize>, //team index, kept while contested, so the zone keeps its color
+    capture_progress: f32, //seconds
+}
+
+impl Component for HillZone {
+    type Storage = DenseVecStorage<Self>;
+}
+
+impl HillZone {
+    pub fn new(index: usize) -> HillZone {
+        HillZone {
+            index,
+            active: false,
+            state: ZoneStates::Neutral,
+            controller: None,
+            capture_progress: 0.0,
+        }
+    }
+
+    pub fn get_state(&self) -> ZoneStates {
+        self.state
+    }
+
+    pub fn get_controller(&self) -> Option<usize> {
+        self.controller
+    }
+
+    // A lone occupant captures the zone after capture_time, and scores while holding it alone.
+    // More than one occupant contests it, freezing capture and scoring.
+    // Occupants are teams, and without team play each player is their own team.
+    // Returns the team scoring this frame.
+    pub fn update(&mut self, occupants: &[usize], capture_time: f32, dt: f32) -> Option<usize> {
+        match occupants {
+            [] => {
+                self.capture_progress = 0.0;
+                self.state = match self.controller {
+                    Some(controller) => ZoneStates::Captured(controller),
+                    None => ZoneStates::Neutral,
+                };
+                None
+            },
+            [occupant] if self.controller == Some(*occupant) => {
+                self.state = ZoneStates::Captured(*occupant);
+                Some(*occupant)
+            },
+            [occupant] => {
+                if self.state != ZoneStates::Capturing(*occupant) {
+                    self.capture_progress = 0.0;
+                }
+                self.capture_progress += dt;
+
+                if self.capture_progress >= capture_time {
+                    self.capture_progress = 0.0;
+                    self.controller = Some(*occupant);
+                    self.state = ZoneStates::Captured(*occupant);
+                }
+                else {
+                    self.state = ZoneStates::Capturing(*occupant);
+                }
+                None
+            },
+            _ => {
+                self.state = ZoneStates::Contested;
+                None
+            },
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.state = ZoneStates::Neutral;
+        self.controller = None;
+        self.capture_progress = 0.0;
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn capture_then_score_until_contested() {
+        let mut zone = HillZone::new(0);
+
+        assert_eq!(zone.update(&[1], 2.0, 1.5), None);
+        assert_eq!(zone.get_state(), ZoneStates::Capturing(1));
+
+        // a different capturer starts over
+        assert_eq!(zone.update(&[2], 2.0, 1.5), None);
+        assert_eq!(zone.update(&[1], 2.0, 1.5), None);
+        assert_eq!(zone.update(&[1], 2.0, 1.0), None);
+        assert_eq!(zone.get_state(), ZoneStates::Captured(1));
+
+        assert_eq!(zone.update(&[1], 2.0, 0.1), Some(1));
+
+        assert_eq!(zone.update(&[1, 2], 2.0, 0.1), None);
+        assert_eq!(zone.get_state(), ZoneStates::Contested);
+        assert_eq!(zone.get_controller(), Some(1));
+
+        // left empty, the controller keeps it without scoring
+        assert_eq!(zone.update(&[], 2.0, 0.1), None);
+        assert_eq!(zone.get_state(), ZoneStates::Captured(1));
+    }
+}
@@ -1,20 +1,51 @@
 use amethyst::{
     core::{transform::Transform, math::Vector3},
-    ecs::prelude::{World},
+    ecs::prelude::{Entities, Entity, Join, LazyUpdate, ReadExpect, World},
     prelude::*,
-    renderer::{SpriteRender},
+    renderer::{palette::Srgba, resources::Tint, SpriteRender, Transparent},
 };
 
-use crate::load_ron_asset;
+use crate::{asset_path, load_ron_asset, try_load_ron_asset};
 
+use std::collections::HashMap;
 use std::f32::consts::PI;
+use std::fs;
+use std::path::PathBuf;
+use std::time::SystemTime;
 
-use crate::components::{ArenaStoreResource, Arena, ArenaElement, ArenaElementKinds, Hitbox, HitboxShape};
+use crate::components::{
+    validate_arena, ArenaStoreResource, GameModeStoreResource, Arena, ArenaElement, ArenaElementKinds, ArenaFloor,
+    ArenaNames, Cooldown, Debris, DestructibleWall, HillZone, Hitbox, HitboxShape, MatchResource, MovingElement,
+    Teleporter, WALL_DAMAGE_STATES};
+use crate::resources::wall_sprite_index;
+
+
+pub const ZONE_NEUTRAL_TINT: [f32; 4] = [1.0, 1.0, 1.0, 0.5];
+pub const RUBBLE_TINT: [f32; 4] = [1.0, 1.0, 1.0, 0.35];
+const TELEPORTER_TINTS: [[f32; 4]; 3] = [ //by pair id, so linked pads match
+    [0.3, 0.9, 1.0, 0.6],
+    [1.0, 0.5, 1.0, 0.6],
+    [1.0, 0.9, 0.3, 0.6],
+];
+
+const DEBRIS_LIFETIME: f32 = 0.6; //seconds
+
+
+const ARENA_PROPERTIES_PATH: [&str; 2] = ["game", "arena_properties.ron"];
+const PROCEDURAL_ARENA_PATH: [&str; 2] = ["game", "procedural_arena.ron"];
+const ARENA_RELOAD_POLL_TIME: f32 = 0.5; //seconds between checks of the arena file
 
 
 pub fn build_arena_store(world: &mut World) {
     world.insert(ArenaStoreResource {
-        properties: load_ron_asset(&["game", "arena_properties.ron"]),
+        properties: load_ron_asset(&ARENA_PROPERTIES_PATH),
+        procedural: load_ron_asset(&PROCEDURAL_ARENA_PATH),
+    });
+}
+
+pub fn build_game_mode_store(world: &mut World) {
+    world.insert(GameModeStoreResource {
+        properties: load_ron_asset(&["game", "arena_game_modes.ron"]),
     });
 }
 
@@ -24,6 +55,7 @@ pub fn intialize_arena(
     arena_properties: &Arena,
     sprite_sheet_handle: &Vec<SpriteRender>,
     texture_sheet_handle: &Vec<SpriteRender>,
+    destroyed_walls: &[usize],
 ) {
     for arena_floor in arena_properties.floor.iter() {
         let sprite_scale_mult = 64.0;
@@ -36,20 +68,26 @@ pub fn intialize_arena(
 
         world
             .create_entity()
+            .with(*arena_floor)
             .with(floor_transform)
             .with(texture_sheet_handle[0].clone())
             .build();
     }
 
-    for arena_element in arena_properties.arena_elements.iter() {
-        let (sprite, x_scale, y_scale) = match arena_element.hitbox.shape {
+    let mut zone_count = 0;
+
+    for (index, arena_element) in arena_properties.arena_elements.iter().enumerate() {
+        // shot down in an earlier round of the match, left as rubble
+        let destroyed = arena_element.health.is_some() && destroyed_walls.contains(&index);
+        let damage_state = if destroyed { WALL_DAMAGE_STATES - 1 } else { 0 };
+        let sprite = sprite_sheet_handle[wall_sprite_index(arena_element.hitbox.shape, damage_state)].clone();
+
+        let (x_scale, y_scale) = match arena_element.hitbox.shape {
             HitboxShape::Circle => (
-                sprite_sheet_handle[5].clone(),
                 arena_element.hitbox.width / 50.0,
                 arena_element.hitbox.height / 50.0,
             ),
             HitboxShape::Rectangle => (
-                sprite_sheet_handle[4].clone(),
                 arena_element.hitbox.width / 10.0,
                 arena_element.hitbox.height / 50.0,
             ),
@@ -66,7 +104,7 @@ pub fn intialize_arena(
             _ => (arena_element.hitbox.width, arena_element.hitbox.height),
         };
 
-        match arena_element.element.kind {
+        let element_entity = match arena_element.element.kind {
             ArenaElementKinds::Wall => {
                 world
                     .create_entity()
@@ -81,7 +119,52 @@ pub fn intialize_arena(
                         arena_element.hitbox.shape))
                     .with(element_transform)
                     .with(sprite)
+                    .build()
+            },
+            ArenaElementKinds::Zone => {
+                let [r, g, b, a] = ZONE_NEUTRAL_TINT;
+
+                let zone_entity = world
+                    .create_entity()
+                    .with(ArenaElement{
+                        kind: arena_element.element.kind,
+                        x: arena_element.element.x,
+                        y: arena_element.element.y,
+                        rotation: arena_element.element.rotation})
+                    .with(Hitbox::new(
+                        hitbox_width,
+                        hitbox_height,
+                        arena_element.hitbox.shape))
+                    .with(HillZone::new(zone_count))
+                    .with(Tint(Srgba::new(r, g, b, a)))
+                    .with(Transparent)
+                    .with(element_transform)
+                    .with(sprite)
                     .build();
+
+                zone_count += 1;
+                zone_entity
+            },
+            ArenaElementKinds::Teleporter(pair_id) => {
+                let [r, g, b, a] = TELEPORTER_TINTS[pair_id as usize % TELEPORTER_TINTS.len()];
+
+                world
+                    .create_entity()
+                    .with(ArenaElement{
+                        kind: arena_element.element.kind,
+                        x: arena_element.element.x,
+                        y: arena_element.element.y,
+                        rotation: arena_element.element.rotation})
+                    .with(Hitbox::new(
+                        hitbox_width,
+                        hitbox_height,
+                        arena_element.hitbox.shape))
+                    .with(Teleporter::new(pair_id, arena_element.element.rotation as f32))
+                    .with(Tint(Srgba::new(r, g, b, a)))
+                    .with(Transparent)
+                    .with(element_transform)
+                    .with(sprite)
+                    .build()
             },
             _ => {
                 world
@@ -93,8 +176,160 @@ pub fn intialize_arena(
                         rotation: arena_element.element.rotation})
                     .with(element_transform)
                     .with(sprite)
-                    .build();
+                    .build()
             }
+        };
+
+        if let Some(motion) = arena_element.motion {
+            world
+                .write_storage::<MovingElement>()
+                .insert(element_entity, MovingElement::new(
+                    motion,
+                    arena_element.element.x,
+                    arena_element.element.y,
+                    arena_element.element.rotation as f32))
+                .expect("Failed to add arena element motion");
         }
+
+        if let (ArenaElementKinds::Wall, Some(health)) = (arena_element.element.kind, arena_element.health) {
+            let mut destructible_wall = DestructibleWall::new(index, health);
+
+            if destroyed {
+                destructible_wall.health.damage(health);
+
+                let [r, g, b, a] = RUBBLE_TINT;
+                world.write_storage::<Hitbox>().remove(element_entity);
+                world
+                    .write_storage::<Tint>()
+                    .insert(element_entity, Tint(Srgba::new(r, g, b, a)))
+                    .expect("Failed to tint rubble");
+                world
+                    .write_storage::<Transparent>()
+                    .insert(element_entity, Transparent)
+                    .expect("Failed to tint rubble");
+            }
+
+            world
+                .write_storage::<DestructibleWall>()
+                .insert(element_entity, destructible_wall)
+                .expect("Failed to add wall health");
+        }
+    }
+}
+
+// A chip flying off a destructible wall, removed by the DestructibleWallSystem once its time is up
+pub fn spawn_wall_debris(
+    entities: &Entities,
+    lazy_update: &ReadExpect<LazyUpdate>,
+    debris_sprite: &SpriteRender,
+    x: f32,
+    y: f32,
+    dx: f32,
+    dy: f32,
+) {
+    let debris_entity: Entity = entities.create();
+
+    let mut debris_transform = Transform::default();
+    debris_transform.set_translation_xyz(x, y, -0.04);
+
+    lazy_update.insert(debris_entity, Debris {
+        dx,
+        dy,
+        spin: (dx - dy) / 10.0,
+        lifetime: DEBRIS_LIFETIME,
+    });
+    lazy_update.insert(debris_entity, debris_transform);
+    lazy_update.insert(debris_entity, debris_sprite.clone());
+}
+
+// Polls the arena file's modified time, so arenas can be edited while the game runs
+pub struct ArenaFileWatcher {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    poll_cooldown: Cooldown,
+}
+
+impl Default for ArenaFileWatcher {
+    fn default() -> ArenaFileWatcher {
+        let path = asset_path(&ARENA_PROPERTIES_PATH);
+        let modified = fs::metadata(&path).and_then(|metadata| metadata.modified()).ok();
+
+        ArenaFileWatcher {
+            path,
+            modified,
+            poll_cooldown: Cooldown::new(ARENA_RELOAD_POLL_TIME, ARENA_RELOAD_POLL_TIME),
+        }
+    }
+}
+
+impl ArenaFileWatcher {
+    // True once for every change to the file
+    pub fn changed(&mut self, dt: f32) -> bool {
+        self.poll_cooldown.timer_update(&dt);
+        if !self.poll_cooldown.timer_active() {
+            return false;
+        }
+        self.poll_cooldown.timer_reset();
+
+        let modified = fs::metadata(&self.path).and_then(|metadata| metadata.modified()).ok();
+        if modified.is_none() || modified == self.modified {
+            return false;
+        }
+
+        self.modified = modified;
+        true
+    }
+}
+
+
+// Reloads the arena store from file and rebuilds the current arena's elements and floor in place.
+// Players and everything else in the round are left alone. A file that fails to load keeps the old arena.
+pub fn reload_arena(
+    world: &mut World,
+    arena_name: &ArenaNames,
+    sprite_sheet_handle: &Vec<SpriteRender>,
+    texture_sheet_handle: &Vec<SpriteRender>,
+) {
+    // generated arenas have no file to follow
+    if let ArenaNames::Procedural(_) = arena_name {
+        return;
+    }
+
+    let arenas: HashMap<ArenaNames, Arena> = match try_load_ron_asset(&ARENA_PROPERTIES_PATH) {
+        Ok(arenas) => arenas,
+        Err(error) => {
+            log::error!("arena reload failed, keeping the current arena: {}", error);
+            return;
+        },
+    };
+
+    let arena_properties = match arenas.get(arena_name) {
+        Some(arena_properties) => arena_properties.clone(),
+        None => {
+            log::error!("arena reload failed, {:?} is missing from the file", arena_name);
+            return;
+        },
+    };
+
+    for issue in validate_arena(&arena_properties) {
+        log::warn!("{:?}: {}", arena_name, issue);
     }
-}
\ No newline at end of file
+
+    let stale_entities: Vec<Entity> = {
+        let entities = world.entities();
+        let arena_elements = world.read_storage::<ArenaElement>();
+        let arena_floors = world.read_storage::<ArenaFloor>();
+
+        (&*entities, &arena_elements).join().map(|(entity, _)| entity)
+            .chain((&*entities, &arena_floors).join().map(|(entity, _)| entity))
+            .collect()
+    };
+    world.delete_entities(&stale_entities).expect("Failed to delete arena entities");
+
+    world.write_resource::<ArenaStoreResource>().properties = arenas;
+
+    let destroyed_walls = world.read_resource::<MatchResource>().get_destroyed_walls(arena_name);
+    intialize_arena(world, &arena_properties, sprite_sheet_handle, texture_sheet_handle, &destroyed_walls);
+
+    log::info!("{:?} reloaded", arena_name);
+}
@@ -0,0 +1,67 @@
+use amethyst::{
+    core::{transform::Transform, math::Vector3},
+    ecs::prelude::{Entities, Entity, LazyUpdate, ReadExpect, World},
+    renderer::{palette::Srgba, resources::Tint},
+};
+
+use crate::load_ron_asset;
+
+use crate::components::{
+    CollisionType, Drone, DroneKinds, DroneProperties, Health, Hitbox, HitboxShape, Mass, Movable, Powerable,
+    SurvivalStoreResource, Weapon, WeaponNames, WeaponProperties};
+use crate::resources::DroneSpriteResource;
+
+
+pub fn build_survival_store(world: &mut World) {
+    world.insert(SurvivalStoreResource {
+        properties: load_ron_asset(&["game", "survival_waves.ron"]),
+    });
+}
+
+
+// Spawned mid-round by the survival waves, so built through LazyUpdate like weapon fire
+pub fn spawn_drone(
+    entities: &Entities,
+    kind: DroneKinds,
+    drone_properties: &DroneProperties,
+    weapon: Option<(WeaponNames, WeaponProperties)>,
+    (x, y): (f32, f32),
+    drone_sprite_resource: &ReadExpect<DroneSpriteResource>,
+    lazy_update: &ReadExpect<LazyUpdate>,
+) {
+    let drone_entity: Entity = entities.create();
+
+    //circle sprite is 50 pixels wide
+    let mut drone_transform = Transform::default();
+    drone_transform.set_translation_xyz(x, y, 0.0);
+    drone_transform.set_scale(Vector3::new(
+        drone_properties.hitbox_diameter / 50.0,
+        drone_properties.hitbox_diameter / 50.0,
+        0.0));
+
+    let (r, g, b, a) = drone_properties.tint;
+
+    lazy_update.insert(drone_entity, Drone::new(kind, drone_properties));
+    lazy_update.insert(drone_entity, Movable::new(
+        Powerable::new(1, 1, 1),
+        drone_properties.max_accel_force,
+        CollisionType::Bounce{bounces: None, sticks: false}));
+    lazy_update.insert(drone_entity, Mass::new(drone_properties.mass));
+    lazy_update.insert(drone_entity, Health::new(drone_properties.health));
+    lazy_update.insert(drone_entity, Hitbox::new(
+        drone_properties.hitbox_diameter,
+        drone_properties.hitbox_diameter,
+        HitboxShape::Circle));
+
+    if let Some((weapon_name, weapon_properties)) = weapon {
+        lazy_update.insert(drone_entity, Weapon::new(
+            weapon_name,
+            &weapon_properties,
+            Powerable::new(1, 1, 1),
+            0.0));
+    }
+
+    lazy_update.insert(drone_entity, drone_sprite_resource.drone_body.clone());
+    lazy_update.insert(drone_entity, Tint(Srgba::new(r, g, b, a)));
+    lazy_update.insert(drone_entity, drone_transform);
+}
@@ -0,0 +1,65 @@
+use amethyst::{
+    core::{transform::Transform, math::Vector3},
+    ecs::prelude::{World},
+    prelude::*,
+    renderer::{palette::Srgba, resources::Tint, SpriteRender, Transparent},
+};
+
+use crate::components::{Arena, Flag, FlagBase, GameModes, Hitbox, HitboxShape};
+use crate::entities::get_team_tint;
+
+
+pub const FLAG_BASE_SIZE: f32 = 30.0;
+pub const FLAG_SIZE: f32 = 12.0;
+
+
+pub fn intialize_flags(
+    world: &mut World,
+    arena_properties: &Arena,
+    game_mode: &GameModes,
+    sprite_sheet_handle: &Vec<SpriteRender>,
+) {
+    if *game_mode != GameModes::CaptureTheFlag {
+        return;
+    }
+
+    for flag_spawn in arena_properties.flags.iter() {
+        let Tint(team_color) = get_team_tint(flag_spawn.team);
+
+        //Create team base, circle sprite is 50 pixels wide
+        let mut base_transform = Transform::default();
+        base_transform.set_translation_xyz(flag_spawn.x, flag_spawn.y, -0.04);
+        base_transform.set_scale(Vector3::new(FLAG_BASE_SIZE / 50.0, FLAG_BASE_SIZE / 50.0, 0.0));
+
+        world
+            .create_entity()
+            .with(base_transform)
+            .with(sprite_sheet_handle[5].clone())
+            .with(Tint(Srgba::new(team_color.red, team_color.green, team_color.blue, 0.4)))
+            .with(Transparent)
+            .with(Hitbox::new(
+                FLAG_BASE_SIZE,
+                FLAG_BASE_SIZE,
+                HitboxShape::Circle))
+            .with(FlagBase{team: flag_spawn.team})
+            .build();
+
+        //Create team flag, drawn above the players so a carried flag stays visible
+        let mut flag_transform = Transform::default();
+        flag_transform.set_translation_xyz(flag_spawn.x, flag_spawn.y, 0.1);
+        flag_transform.set_scale(Vector3::new(FLAG_SIZE / 50.0, FLAG_SIZE / 50.0, 0.0));
+
+        world
+            .create_entity()
+            .with(flag_transform)
+            .with(sprite_sheet_handle[5].clone())
+            .with(get_team_tint(flag_spawn.team))
+            .with(Transparent)
+            .with(Hitbox::new(
+                FLAG_SIZE,
+                FLAG_SIZE,
+                HitboxShape::Circle))
+            .with(Flag::new(flag_spawn.team, flag_spawn.x, flag_spawn.y))
+            .build();
+    }
+}
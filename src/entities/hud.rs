@@ -0,0 +1,106 @@
+use amethyst::{
+    assets::{Loader},
+    ecs::prelude::{World},
+    prelude::*,
+    ui::{Anchor, FontHandle, LineMode, TtfFormat, UiText, UiTransform},
+};
+
+use crate::components::{KillFeedHud, MatchHud, PlayerHud};
+
+pub fn initialize_hud(world: &mut World, player_count: usize) {
+    let font: FontHandle = world.read_resource::<Loader>().load(
+        "fonts/square.ttf",
+        TtfFormat,
+        (),
+        &world.read_resource(),
+    );
+
+    for player_id in 0..player_count {
+        world
+            .create_entity()
+            .with(UiTransform::new(
+                format!("player_{}_hud", player_id + 1),
+                Anchor::BottomLeft,
+                Anchor::BottomLeft,
+                5.,
+                5. + 25. * (player_count - 1 - player_id) as f32,
+                1.,
+                500.,
+                25.,
+            ))
+            .with(UiText::new(
+                font.clone(),
+                "".to_string(),
+                [1., 1., 1., 1.],
+                20.,
+                LineMode::Single,
+                Anchor::MiddleLeft,
+            ))
+            .with(PlayerHud{id: player_id})
+            .build();
+    }
+}
+
+pub fn initialize_match_hud(world: &mut World) {
+    let font: FontHandle = world.read_resource::<Loader>().load(
+        "fonts/square.ttf",
+        TtfFormat,
+        (),
+        &world.read_resource(),
+    );
+
+    world
+        .create_entity()
+        .with(UiTransform::new(
+            "match_hud".to_string(),
+            Anchor::TopMiddle,
+            Anchor::TopMiddle,
+            0.,
+            -5.,
+            1.,
+            500.,
+            250.,
+        ))
+        .with(UiText::new(
+            font,
+            "".to_string(),
+            [1., 1., 1., 1.],
+            20.,
+            LineMode::Wrap,
+            Anchor::TopMiddle,
+        ))
+        .with(MatchHud)
+        .build();
+}
+
+pub fn initialize_kill_feed_hud(world: &mut World) {
+    let font: FontHandle = world.read_resource::<Loader>().load(
+        "fonts/square.ttf",
+        TtfFormat,
+        (),
+        &world.read_resource(),
+    );
+
+    world
+        .create_entity()
+        .with(UiTransform::new(
+            "kill_feed_hud".to_string(),
+            Anchor::TopRight,
+            Anchor::TopRight,
+            -5.,
+            -5.,
+            1.,
+            300.,
+            100.,
+        ))
+        .with(UiText::new(
+            font,
+            "".to_string(),
+            [1., 1., 1., 1.],
+            16.,
+            LineMode::Wrap,
+            Anchor::TopRight,
+        ))
+        .with(KillFeedHud)
+        .build();
+}
@@ -2,8 +2,22 @@ mod arena;
 mod camera;
 mod player;
 mod weapon_fire;
+mod pickup;
+mod hud;
+mod flag;
+mod race;
+mod drone;
+mod safe_zone;
 
-pub use self::arena::{build_arena_store, intialize_arena};
+pub use self::arena::{
+    build_arena_store, build_game_mode_store, intialize_arena, reload_arena, spawn_wall_debris, ArenaFileWatcher,
+    RUBBLE_TINT, ZONE_NEUTRAL_TINT};
 pub use self::camera::{initialize_camera};
-pub use self::player::{intialize_player};
-pub use self::weapon_fire::{fire_weapon};
\ No newline at end of file
+pub use self::player::{build_player_profiles, build_ship_store, get_player_tint, get_team_tint, intialize_player};
+pub use self::weapon_fire::{build_weapon_store, fire_weapon};
+pub use self::pickup::{build_pickup_store, get_pickup_tint, intialize_pickups};
+pub use self::hud::{initialize_hud, initialize_kill_feed_hud, initialize_match_hud};
+pub use self::flag::{intialize_flags};
+pub use self::race::{intialize_checkpoints};
+pub use self::drone::{build_survival_store, spawn_drone};
+pub use self::safe_zone::{intialize_safe_zone_edges};
\ No newline at end of file
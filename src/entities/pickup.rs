@@ -0,0 +1,82 @@
+use amethyst::{
+    core::{transform::Transform, math::Vector3},
+    ecs::prelude::{World},
+    prelude::*,
+    renderer::{palette::Srgba, resources::Tint, SpriteRender, Transparent},
+};
+
+use crate::load_ron_asset;
+
+use crate::components::{
+    Arena, GameModes, Hitbox, HitboxShape, Pickup, PickupKinds,
+    PickupModeProperties, PickupStoreResource};
+
+
+pub fn build_pickup_store(world: &mut World) {
+    world.insert(PickupStoreResource {
+        properties: load_ron_asset(&["game", "pickup_properties.ron"]),
+    });
+}
+
+
+pub fn get_pickup_tint(kind: &PickupKinds) -> Tint {
+    match kind {
+        PickupKinds::Repair => Tint(Srgba::new(0.2, 1.0, 0.2, 1.0)),
+        PickupKinds::ShieldOvercharge => Tint(Srgba::new(0.2, 0.6, 1.0, 1.0)),
+        PickupKinds::WeaponSwap(_) => Tint(Srgba::new(1.0, 0.3, 0.2, 1.0)),
+        PickupKinds::SpeedBoost => Tint(Srgba::new(1.0, 0.9, 0.2, 1.0)),
+    }
+}
+
+
+pub fn intialize_pickups(
+    world: &mut World,
+    arena_properties: &Arena,
+    game_mode: &GameModes,
+    sprite_sheet_handle: &Vec<SpriteRender>,
+) {
+    let mode_properties = {
+        let fetched_pickup_store = world.try_fetch::<PickupStoreResource>();
+
+        if let Some(pickup_store) = fetched_pickup_store {
+            match pickup_store.properties.get(game_mode) {
+                Some(mode_props_get) => *mode_props_get,
+                _ => PickupModeProperties::default(),
+            }
+        } else {
+            PickupModeProperties::default()
+        }
+    };
+
+    for pickup_spawn in arena_properties.pickup_spawns.iter() {
+        let kinds: Vec<PickupKinds> = pickup_spawn.kinds
+            .iter()
+            .filter(|kind| mode_properties.kind_enabled(kind))
+            .cloned()
+            .collect();
+
+        if kinds.is_empty() {
+            continue; //nothing this game mode allows at this spawn point
+        }
+
+        let pickup = Pickup::new(kinds, pickup_spawn.respawn_time * mode_properties.respawn_time_mult);
+        let tint = get_pickup_tint(&pickup.kind());
+
+        let mut pickup_transform = Transform::default();
+        pickup_transform.set_translation_xyz(pickup_spawn.x, pickup_spawn.y, -0.02);
+        pickup_transform.set_scale(Vector3::new(0.2, 0.2, 0.0));
+
+        world
+            .create_entity()
+            .with(pickup_transform)
+            .with(sprite_sheet_handle[5].clone())
+            .with(tint)
+            .with(Transparent)
+            .with(Hitbox::new(
+                10.0,
+                10.0,
+                HitboxShape::Circle))
+            .with(pickup)
+            .build();
+    }
+}
@@ -2,23 +2,83 @@ use amethyst::{
     core::{transform::Transform, math::Vector3, components::Parent},
     ecs::prelude::{World},
     prelude::*,
-    renderer::{SpriteRender, Transparent},
+    renderer::{palette::Srgba, resources::Tint, SpriteRender, Transparent},
 };
 
 use std::f32::consts::PI;
 
+use crate::load_ron_asset;
+
 use crate::components::{
-    Arena, Movable, CollisionType, Mass,
-    Player, PlayerState, AimControlState, Hitbox, HitboxShape, Weapon, WeaponAimChild, Shield, ShieldAimChild, Powerable, Cooldown};
+    Movable, CollisionType, Mass, Health, PickupBoosts, StatusEffects,
+    Player, PlayerState, AimControlState, Hitbox, HitboxShape, Weapon, WeaponAimChild, WeaponNames, WeaponStoreResource,
+    Shield, ShieldAimChild, PowerBus, PowerPresets, PowerSubsystems,
+    PlayerProfileResource, PlayerSpawnPoint, ShipStoreResource, LobbyResource, MatchResource, Handicap, Cooldown};
+
+pub fn build_player_profiles(world: &mut World) {
+    world.insert(PlayerProfileResource {
+        profiles: load_ron_asset(&["game", "player_profiles.ron"]),
+    });
+}
+
+// Teams take the first two player colors
+pub fn get_team_tint(team: usize) -> Tint {
+    get_player_tint(team)
+}
+
+// Matches the body sprite colors, for anything tinted by player
+pub fn get_player_tint(player_id: usize) -> Tint {
+    match player_id {
+        0 => Tint(Srgba::new(1.0, 0.3, 0.3, 1.0)),
+        1 => Tint(Srgba::new(0.3, 0.5, 1.0, 1.0)),
+        2 => Tint(Srgba::new(0.3, 1.0, 0.3, 1.0)),
+        3 => Tint(Srgba::new(1.0, 0.9, 0.2, 1.0)),
+        _ => Tint(Srgba::new(1.0, 1.0, 1.0, 1.0)),
+    }
+}
+
+pub fn build_ship_store(world: &mut World) {
+    world.insert(ShipStoreResource {
+        properties: load_ron_asset(&["game", "ships.ron"]),
+    });
+}
+
 
 pub fn intialize_player(
     world: &mut World,
-    arena_properties: &Arena,
+    spawn_points: &[PlayerSpawnPoint],
     sprite_sheet_handle: &Vec<SpriteRender>,
 ) {
-    for (player_id, player_spawn_point) in arena_properties.player_spawn_points.iter().enumerate() {
-        let x_scale = 0.5;
-        let y_scale = 0.5;
+    //started without the lobby, everyone gets the default ship
+    let lobby = match world.try_fetch::<LobbyResource>() {
+        Some(lobby) => (*lobby).clone(),
+        None => LobbyResource::default(),
+    };
+
+    let teams = match world.try_fetch::<MatchResource>() {
+        Some(match_resource) => match_resource.setup.teams,
+        None => false,
+    };
+
+    for (player_id, player_spawn_point) in spawn_points.iter().enumerate() {
+        let ship_class = lobby.get_player(player_id).ship_class;
+        let (player_team, handicap) = match world.try_fetch::<MatchResource>() {
+            Some(match_resource) => (match_resource.get_team(player_id), match_resource.get_handicap(player_id)),
+            None => (player_id, Handicap::default()),
+        };
+        let ship_properties = {
+            let ship_store = world.read_resource::<ShipStoreResource>();
+            ship_store.get_properties(&ship_class)
+        };
+
+        let weapon_name: WeaponNames = ship_properties.weapon;
+        let weapon_properties = {
+            let weapon_store = world.read_resource::<WeaponStoreResource>();
+            weapon_store.get_properties(&weapon_name)
+        };
+
+        let x_scale = ship_properties.sprite_scale;
+        let y_scale = ship_properties.sprite_scale;
 
         let player_rotation = player_spawn_point.rotation / 180.0 * PI;
 
@@ -28,39 +88,61 @@ pub fn intialize_player(
         player_transform.set_translation_xyz(player_spawn_point.x, player_spawn_point.y, 0.0);
         player_transform.set_scale(Vector3::new(x_scale, y_scale, 0.0));
 
-        let power = 9;
+        let power_bus = PowerBus::new(&ship_properties.power).with_handicap(handicap.power_budget);
+        let profile = {
+            let profile_store = world.read_resource::<PlayerProfileResource>();
+            profile_store.get_profile(player_id)
+        };
+
+        // each ship class has its own body, tinted by the team in team play or else the player
+        let body_sprite = match sprite_sheet_handle.get(ship_properties.sprite_index) {
+            Some(sprite) => sprite.clone(),
+            None => sprite_sheet_handle[18].clone(),
+        };
+        let body_tint = if teams {
+            get_team_tint(player_team)
+        } else {
+            get_player_tint(player_id)
+        };
 
         let proton_body = world
             .create_entity()
             .with(player_transform)
-            .with(sprite_sheet_handle[player_id].clone())
+            .with(body_sprite)
+            .with(body_tint)
             .with(Transparent)
             .with(Player::new(
-                player_id, 
+                player_id,
+                player_team,
                 0.1, 
-                0.1))
+                0.1,
+                1.5,
+                1.0))
             .with(Movable::new(
-                9,
-                300.0,
+                power_bus.create_powerable(&PowerSubsystems::Thrust),
+                ship_properties.max_accel_force,
                 CollisionType::Bounce{bounces:None, sticks:false}))
-            .with(Mass::new(1.0))
+            .with(Mass::new(ship_properties.mass))
+            .with(Health::new(ship_properties.health))
+            .with(PickupBoosts::default())
+            .with(StatusEffects::default())
             .with(Hitbox::new(
-                16.0 * x_scale,
-                16.0 * y_scale,
+                ship_properties.hitbox_diameter,
+                ship_properties.hitbox_diameter,
                 HitboxShape::Circle))
             .with(Shield{
                 cooldown: Cooldown::new(0.0, 0.333),
-                power: Powerable::new(power, power),
-                angle: player_rotation})
-            .with(Weapon{
-                cooldown: Cooldown::new(0.0, 0.333),
-                power: Powerable::new(power, power),
-                shot_speed: 300.0,
-                damage: 10.0,
+                power: power_bus.create_powerable(&PowerSubsystems::Shield),
                 angle: player_rotation})
+            .with(Weapon::new(
+                weapon_name,
+                &weapon_properties,
+                power_bus.create_powerable(&PowerSubsystems::Weapon),
+                player_rotation))
+            .with(power_bus)
+            .with(PowerPresets::new(&profile))
             .build();
 
-
         //Create player proton shield
         let mut shield_transform = Transform::default();
         shield_transform.set_rotation_2d(0.0);
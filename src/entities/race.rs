@@ -0,0 +1,70 @@
+use amethyst::{
+    core::{transform::Transform, math::Vector3},
+    ecs::prelude::{Entity, Join, World},
+    prelude::*,
+    renderer::{palette::Srgba, resources::Tint, SpriteRender, Transparent},
+};
+
+use std::f32::consts::PI;
+
+use crate::components::{Arena, Checkpoint, GameModes, Hitbox, HitboxShape, Player, RaceProgress};
+
+
+const START_LINE_TINT: [f32; 4] = [1.0, 1.0, 1.0, 0.6];
+const CHECKPOINT_TINT: [f32; 4] = [1.0, 1.0, 0.4, 0.25];
+
+
+// Checkpoints are only built for Race, which also gives every player their race progress,
+// so players have to be initialized first
+pub fn intialize_checkpoints(
+    world: &mut World,
+    arena_properties: &Arena,
+    game_mode: &GameModes,
+    sprite_sheet_handle: &Vec<SpriteRender>,
+) {
+    if *game_mode != GameModes::Race {
+        return;
+    }
+
+    for (index, checkpoint) in arena_properties.checkpoints.iter().enumerate() {
+        //Rectangle sprite is 10 pixels wide and 50 pixels tall
+        let mut checkpoint_transform = Transform::default();
+        checkpoint_transform.set_rotation_2d(checkpoint.rotation as f32 / 180.0 * PI);
+        checkpoint_transform.set_translation_xyz(checkpoint.x, checkpoint.y, -0.04);
+        checkpoint_transform.set_scale(Vector3::new(checkpoint.width / 10.0, checkpoint.height / 50.0, 0.0));
+
+        let (hitbox_width, hitbox_height) = match checkpoint.rotation {
+            90 | 270 => (checkpoint.height, checkpoint.width),
+            _ => (checkpoint.width, checkpoint.height),
+        };
+
+        let [r, g, b, a] = if index == 0 {
+            START_LINE_TINT
+        } else {
+            CHECKPOINT_TINT
+        };
+
+        world
+            .create_entity()
+            .with(checkpoint_transform)
+            .with(sprite_sheet_handle[4].clone())
+            .with(Tint(Srgba::new(r, g, b, a)))
+            .with(Transparent)
+            .with(Hitbox::new(
+                hitbox_width,
+                hitbox_height,
+                HitboxShape::Rectangle))
+            .with(Checkpoint{index})
+            .build();
+    }
+
+    let player_entities: Vec<Entity> = (&world.entities(), &world.read_storage::<Player>())
+        .join()
+        .map(|(entity, _)| entity)
+        .collect();
+
+    let mut race_progresses = world.write_storage::<RaceProgress>();
+    for entity in player_entities {
+        let _ = race_progresses.insert(entity, RaceProgress::default());
+    }
+}
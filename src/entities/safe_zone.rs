@@ -0,0 +1,38 @@
+use amethyst::{
+    core::{transform::Transform, HiddenPropagate},
+    ecs::prelude::{World},
+    prelude::*,
+    renderer::{palette::Srgba, resources::Tint, SpriteRender, Transparent},
+};
+
+use crate::components::{SafeZoneEdge, SuddenDeathRules, SAFE_ZONE_SIDES};
+
+
+const SAFE_ZONE_EDGE_TINT: [f32; 4] = [1.0, 0.2, 0.1, 0.7];
+
+
+// Only built when the mode's sudden death shrinks the arena, placed by the SuddenDeathSystem
+pub fn intialize_safe_zone_edges(
+    world: &mut World,
+    sudden_death: &SuddenDeathRules,
+    sprite_sheet_handle: &Vec<SpriteRender>,
+) {
+    match sudden_death {
+        SuddenDeathRules::ShrinkingArena(_) => {},
+        _ => return,
+    }
+
+    let [r, g, b, a] = SAFE_ZONE_EDGE_TINT;
+
+    for side in SAFE_ZONE_SIDES.iter() {
+        world
+            .create_entity()
+            .with(Transform::default())
+            .with(sprite_sheet_handle[4].clone())
+            .with(Tint(Srgba::new(r, g, b, a)))
+            .with(Transparent)
+            .with(HiddenPropagate::new())
+            .with(SafeZoneEdge{side: *side})
+            .build();
+    }
+}
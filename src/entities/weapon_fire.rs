@@ -1,26 +1,44 @@
 use amethyst::{
     core::transform::Transform,
-    ecs::prelude::{Entities, Entity, LazyUpdate, ReadExpect},
+    ecs::prelude::{Entities, Entity, LazyUpdate, ReadExpect, World},
+    renderer::{palette::Srgba, resources::Tint},
     utils::removal::Removal,
 };
 
+use crate::load_ron_asset;
+
 use std::f32::consts::PI;
 
-use crate::components::{CollisionType, Movable, Weapon, WeaponFire, Hitbox, HitboxShape, Mass, Powerable};
+use crate::components::{
+    CollisionType, Movable, Weapon, WeaponFire, WeaponStoreResource, Hitbox, HitboxShape, Mass, Powerable};
 use crate::resources::WeaponFireResource;
 
+
+pub fn build_weapon_store(world: &mut World) {
+    world.insert(WeaponStoreResource {
+        properties: load_ron_asset(&["game", "weapon_properties.ron"]),
+    });
+}
+
+
+// Returns the new weapon fire entity, for callers adding to it.
+// Shots without a player_id come from drones.
 pub fn fire_weapon(
     entities: &Entities,
     entity_id: u32,
-    player_id: usize,
+    player_id: Option<usize>,
     player_transform: &Transform,
     weapon: &Weapon,
     weapon_fire_resource: &ReadExpect<WeaponFireResource>,
     lazy_update: &ReadExpect<LazyUpdate>,
-) {
+) -> Entity {
     let weapon_fire_entity: Entity = entities.create();
 
-    let weapon_fire = WeaponFire{};
+    let weapon_fire = WeaponFire{
+        owner_id: player_id,
+        damage: weapon.damage,
+        status_effect: weapon.status_effect,
+    };
 
     let (local_transform, weapon_fire_movable) = {
         let mut local_transform = Transform::default();
@@ -32,9 +50,9 @@ pub fn fire_weapon(
         let weapon_fire_movable = Movable{
             dx: weapon.shot_speed * -weapon.angle.sin(),
             dy: weapon.shot_speed * weapon.angle.cos(),
-            power: Powerable::new(1, 1),
+            power: Powerable::new(1, 1, 1),
             max_accel_force: 0.0,
-            collision_type: CollisionType::Bounce{bounces: Some(2), sticks: false},
+            collision_type: CollisionType::Bounce{bounces: Some(weapon.bounces), sticks: false},
             prevent_collision_id: Some(entity_id),
         };
 
@@ -50,10 +68,10 @@ pub fn fire_weapon(
     let weapon_fire_mass = Mass{mass: 0.01};
 
     let weapon_sprite = match player_id {
-        0 => weapon_fire_resource.player_1_weapon_fire.clone(),
-        1 => weapon_fire_resource.player_2_weapon_fire.clone(),
-        2 => weapon_fire_resource.player_3_weapon_fire.clone(),
-        3 => weapon_fire_resource.player_4_weapon_fire.clone(),
+        Some(0) => weapon_fire_resource.player_1_weapon_fire.clone(),
+        Some(1) => weapon_fire_resource.player_2_weapon_fire.clone(),
+        Some(2) => weapon_fire_resource.player_3_weapon_fire.clone(),
+        Some(3) => weapon_fire_resource.player_4_weapon_fire.clone(),
         _ => weapon_fire_resource.player_1_weapon_fire.clone(),
     };
 
@@ -63,7 +81,13 @@ pub fn fire_weapon(
     lazy_update.insert(weapon_fire_entity, weapon_fire_mass);
 
     lazy_update.insert(weapon_fire_entity, weapon_sprite);
+    if player_id.is_none() {
+        //drone shots reuse the player 1 sprite, darkened
+        lazy_update.insert(weapon_fire_entity, Tint(Srgba::new(0.4, 0.4, 0.4, 1.0)));
+    }
     lazy_update.insert(weapon_fire_entity, local_transform);
 
     lazy_update.insert(weapon_fire_entity, Removal::new(0 as u32));
+
+    weapon_fire_entity
 }
@@ -0,0 +1,332 @@
+use amethyst::{
+    assets::{Loader},
+    ecs::prelude::{Entity},
+    input::{is_close_requested, is_key_down, InputHandler, StringBindings, VirtualKeyCode},
+    prelude::*,
+    ui::{Anchor, FontHandle, LineMode, TtfFormat, UiText, UiTransform},
+};
+
+use crate::components::{
+    balance_teams, next_team_request, GameModeStoreResource, LobbyResource, MatchResource, PlayerLobbySettings,
+    HANDICAP_SETTINGS, MAX_PLAYERS};
+use crate::entities::build_game_mode_store;
+use crate::state::MyState;
+
+
+const LOBBY_PREV: usize = 0;
+const LOBBY_NEXT: usize = 1;
+const LOBBY_READY: usize = 2;
+const LOBBY_MODE_NEXT: usize = 3;
+const LOBBY_MODE_PREV: usize = 4;
+const LOBBY_TEAM: usize = 5;
+const LOBBY_TEAMS_TOGGLE: usize = 6;
+const LOBBY_HANDICAP: usize = 7;
+const LOBBY_HANDICAP_SETTING_PREV: usize = 8;
+const LOBBY_HANDICAP_SETTING_NEXT: usize = 9;
+const LOBBY_INPUTS: usize = 10;
+
+
+/// Pre-match lobby where each player picks a ship class and readies up, and player 1 picks the game mode.
+/// Player 1 can also switch team play on or off, and each player can pick a team or be balanced in.
+/// Each player can also set handicap multipliers to even out mixed-skill matches,
+/// picking one with the left stick and stepping its value with the trigger.
+/// Players join with any button, and the match starts once at least two have joined and every
+/// joined player is ready, or on Enter for development.
+#[derive(Default)]
+pub struct LobbyState {
+    lobby: LobbyResource,
+    mode_text: Option<Entity>,
+    player_texts: Vec<Entity>,
+    ui_entities: Vec<Entity>,
+    prev_inputs: [[bool; LOBBY_INPUTS]; MAX_PLAYERS],
+    start: bool,
+}
+
+
+impl SimpleState for LobbyState {
+    fn on_start(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        let world = data.world;
+
+        build_game_mode_store(world);
+
+        // back from a match, keep everyone's previous choices
+        if let Some(lobby) = world.try_fetch::<LobbyResource>() {
+            self.lobby = (*lobby).clone();
+        }
+        for player_settings in self.lobby.players.iter_mut() {
+            player_settings.ready = false;
+        }
+
+        let font: FontHandle = world.read_resource::<Loader>().load(
+            "fonts/square.ttf",
+            TtfFormat,
+            (),
+            &world.read_resource(),
+        );
+
+        let title = world
+            .create_entity()
+            .with(UiTransform::new(
+                "lobby_title".to_string(),
+                Anchor::TopMiddle,
+                Anchor::TopMiddle,
+                0.,
+                -40.,
+                1.,
+                800.,
+                50.,
+            ))
+            .with(UiText::new(
+                font.clone(),
+                "Proton Rally! - Left/Right: Ship, RB: Team, Stick + RT: Handicap, A: Ready".to_string(),
+                [1., 1., 1., 1.],
+                30.,
+                LineMode::Single,
+                Anchor::Middle,
+            ))
+            .build();
+        self.ui_entities.push(title);
+
+        let mode_entity = world
+            .create_entity()
+            .with(UiTransform::new(
+                "lobby_mode".to_string(),
+                Anchor::TopMiddle,
+                Anchor::TopMiddle,
+                0.,
+                -80.,
+                1.,
+                800.,
+                40.,
+            ))
+            .with(UiText::new(
+                font.clone(),
+                mode_text(&self.lobby),
+                [1., 1., 1., 1.],
+                25.,
+                LineMode::Single,
+                Anchor::Middle,
+            ))
+            .build();
+        self.mode_text = Some(mode_entity);
+        self.ui_entities.push(mode_entity);
+
+        for player_id in 0..MAX_PLAYERS {
+            let player_text = world
+                .create_entity()
+                .with(UiTransform::new(
+                    format!("lobby_player_{}", player_id + 1),
+                    Anchor::TopMiddle,
+                    Anchor::TopMiddle,
+                    0.,
+                    -140. - 40. * player_id as f32,
+                    1.,
+                    800.,
+                    40.,
+                ))
+                .with(UiText::new(
+                    font.clone(),
+                    lobby_text(player_id, &self.lobby),
+                    [1., 1., 1., 1.],
+                    25.,
+                    LineMode::Single,
+                    Anchor::Middle,
+                ))
+                .build();
+            self.player_texts.push(player_text);
+            self.ui_entities.push(player_text);
+        }
+    }
+
+    fn on_stop(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        data.world.delete_entities(&self.ui_entities).expect("Failed to delete lobby ui");
+        self.ui_entities.clear();
+        self.player_texts.clear();
+        self.mode_text = None;
+    }
+
+    fn handle_event(
+        &mut self,
+        mut _data: StateData<'_, GameData<'_, '_>>,
+        event: StateEvent,
+    ) -> SimpleTrans {
+        if let StateEvent::Window(event) = &event {
+            if is_close_requested(&event) || is_key_down(&event, VirtualKeyCode::Escape) {
+                return Trans::Quit;
+            }
+
+            // keyboard override for development
+            if is_key_down(&event, VirtualKeyCode::Return) {
+                self.start = true;
+            }
+        }
+
+        Trans::None
+    }
+
+    fn update(&mut self, data: &mut StateData<'_, GameData<'_, '_>>) -> SimpleTrans {
+        let inputs: Vec<[bool; LOBBY_INPUTS]> = {
+            let input = data.world.read_resource::<InputHandler<StringBindings>>();
+
+            (0..MAX_PLAYERS).map(|player_id| {
+                let prefix = format!("p{}", player_id + 1);
+                let is_down = |action: &str| {
+                    input.action_is_down(&format!("{}_{}", prefix, action)).unwrap_or(false)
+                };
+                // the trigger and stick are axes, pushed past halfway counts as a press
+                let axis_value = |axis: &str| {
+                    input.axis_value(&format!("{}_{}", prefix, axis)).unwrap_or(0.0)
+                };
+
+                [
+                    is_down("adjust_shield_system"),
+                    is_down("adjust_weapon_system"),
+                    is_down("dash"),
+                    is_down("adjust_thrust_system"),
+                    is_down("adjust_reset_system"),
+                    is_down("aim_weapon_state"),
+                    is_down("aim_shield_state"),
+                    axis_value("fire") > 0.5,
+                    axis_value("move_y") > 0.5,
+                    axis_value("move_y") < -0.5,
+                ]
+            }).collect()
+        };
+
+        for (player_id, player_input) in inputs.iter().enumerate() {
+            let prev_input = self.prev_inputs[player_id];
+            let pressed = |idx: usize| player_input[idx] && !prev_input[idx];
+
+            // the first press only joins
+            if !self.lobby.players[player_id].joined {
+                if (0..LOBBY_INPUTS).any(pressed) {
+                    self.lobby.players[player_id].joined = true;
+                }
+                self.prev_inputs[player_id] = *player_input;
+                continue;
+            }
+
+            // player 1 hosts, and picks the game mode for everyone
+            if player_id == 0 {
+                let mode_before = self.lobby.game_mode;
+
+                if pressed(LOBBY_MODE_NEXT) {
+                    self.lobby.game_mode = self.lobby.game_mode.next();
+                }
+                if pressed(LOBBY_MODE_PREV) {
+                    self.lobby.game_mode = self.lobby.game_mode.prev();
+                }
+                if self.lobby.game_mode != mode_before {
+                    let game_mode_store = data.world.read_resource::<GameModeStoreResource>();
+                    self.lobby.teams = game_mode_store.get_setup(&self.lobby.game_mode).teams;
+                }
+
+                if pressed(LOBBY_TEAMS_TOGGLE) {
+                    self.lobby.teams = !self.lobby.teams;
+                }
+            }
+
+            let player_settings = &mut self.lobby.players[player_id];
+
+            if !player_settings.ready {
+                if pressed(LOBBY_PREV) {
+                    player_settings.ship_class = player_settings.ship_class.prev();
+                }
+                if pressed(LOBBY_NEXT) {
+                    player_settings.ship_class = player_settings.ship_class.next();
+                }
+                if pressed(LOBBY_TEAM) && self.lobby.teams {
+                    player_settings.team = next_team_request(player_settings.team);
+                }
+                if pressed(LOBBY_HANDICAP_SETTING_PREV) {
+                    player_settings.handicap_setting = player_settings.handicap_setting.prev();
+                }
+                if pressed(LOBBY_HANDICAP_SETTING_NEXT) {
+                    player_settings.handicap_setting = player_settings.handicap_setting.next();
+                }
+                if pressed(LOBBY_HANDICAP) {
+                    player_settings.handicap.step(player_settings.handicap_setting);
+                }
+            }
+            if pressed(LOBBY_READY) {
+                player_settings.ready = !player_settings.ready;
+            }
+
+            self.prev_inputs[player_id] = *player_input;
+        }
+
+        {
+            let mut ui_texts = data.world.write_storage::<UiText>();
+            if let Some(ui_text) = self.mode_text.and_then(|mode_entity| ui_texts.get_mut(mode_entity)) {
+                ui_text.text = mode_text(&self.lobby);
+            }
+            for (player_id, player_text) in self.player_texts.iter().enumerate() {
+                if let Some(ui_text) = ui_texts.get_mut(*player_text) {
+                    ui_text.text = lobby_text(player_id, &self.lobby);
+                }
+            }
+        }
+
+        if self.start || self.lobby.can_start() {
+            let mut setup = data.world.read_resource::<GameModeStoreResource>().get_setup(&self.lobby.game_mode);
+            setup.teams = self.lobby.teams;
+
+            let mut match_resource = MatchResource::new(self.lobby.game_mode, setup, MAX_PLAYERS);
+            match_resource.assign_teams(&self.lobby.team_requests());
+            match_resource.handicaps = self.lobby.handicaps();
+
+            data.world.insert(match_resource);
+            data.world.insert(self.lobby.clone());
+            return Trans::Switch(Box::new(MyState::default()));
+        }
+
+        Trans::None
+    }
+}
+
+
+fn mode_text(lobby: &LobbyResource) -> String {
+    format!(
+        "P1 Up/Down: < {:?} >  LB: {}",
+        lobby.game_mode,
+        if lobby.teams { "Teams" } else { "Free-for-all" },
+    )
+}
+
+// Shows the team each player will end up on, with auto picks already balanced
+fn lobby_text(player_id: usize, lobby: &LobbyResource) -> String {
+    let player_settings: PlayerLobbySettings = lobby.get_player(player_id);
+
+    if !player_settings.joined {
+        return format!("P{}  Press any button to join", player_id + 1);
+    }
+
+    let team_text = if lobby.teams {
+        let team = balance_teams(&lobby.team_requests())[player_id];
+        match player_settings.team {
+            Some(_) => format!("Team {}", team + 1),
+            None => format!("Team {} (auto)", team + 1),
+        }
+    } else {
+        "".to_string()
+    };
+
+    // the multiplier being set, and any others already changed
+    let setting = player_settings.handicap_setting;
+    let mut handicap_text = format!("[{} x{}]", setting.label(), player_settings.handicap.get(setting));
+    for other_setting in HANDICAP_SETTINGS.iter().filter(|other_setting| **other_setting != setting) {
+        let value = player_settings.handicap.get(*other_setting);
+        if value != 1.0 {
+            handicap_text.push_str(&format!(" {} x{}", other_setting.label(), value));
+        }
+    }
+
+    format!(
+        "P{}  < {:?} >  {}  {}  {}",
+        player_id + 1,
+        player_settings.ship_class,
+        team_text,
+        handicap_text,
+        if player_settings.ready { "READY" } else { "" },
+    )
+}
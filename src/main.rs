@@ -11,12 +11,14 @@ use amethyst::{
     utils::{application_dir, application_root_dir, fps_counter::FpsCounterBundle}
 };
 
+use std::collections::HashMap;
 use std::fs::File;
 use std::path::PathBuf;
 
 use serde::de::DeserializeOwned;
 
 mod state;
+mod lobby_state;
 
 mod entities;
 mod components;
@@ -24,21 +26,81 @@ mod systems;
 mod resources;
 
 
-fn load_ron_asset<T: DeserializeOwned>(path: &[&str]) -> T {
+fn asset_path(path: &[&str]) -> PathBuf {
     let mut path_buf = PathBuf::from("assets");
     path_buf.extend(path);
-    let path = application_dir(path_buf).expect("Failed to find application directory");
+    application_dir(path_buf).expect("Failed to find application directory")
+}
+
+fn load_ron_asset<T: DeserializeOwned>(path: &[&str]) -> T {
+    let path = asset_path(path);
 
     let file = File::open(&path).expect(&format!("Failed to open file: {:?}", path));
 
     ron::de::from_reader(file).expect("Failed to load config")
 }
 
+// For assets edited while the game runs, a broken edit is reported instead of crashing
+fn try_load_ron_asset<T: DeserializeOwned>(path: &[&str]) -> Result<T, String> {
+    let path = asset_path(path);
+
+    let file = File::open(&path).map_err(|error| format!("Failed to open file {:?}: {}", path, error))?;
+
+    ron::de::from_reader(file).map_err(|error| format!("Failed to load {:?}: {}", path, error))
+}
+
+
+// Reports problems in every arena without starting the game, exits with an error if any arena is broken
+fn validate_arenas() -> bool {
+    let arenas: HashMap<components::ArenaNames, components::Arena> = load_ron_asset(&["game", "arena_properties.ron"]);
+    let mut valid = true;
+
+    for (arena_name, issues) in components::validate_arenas(&arenas) {
+        if issues.is_empty() {
+            println!("{:?}: ok", arena_name);
+        }
+        for issue in issues.iter() {
+            println!("{:?}: {}", arena_name, issue);
+            valid &= !issue.is_error();
+        }
+    }
+
+    valid
+}
+
+
+// Prints a generated arena as RON, so a good seed can be kept and tuned by hand in arena_properties.ron
+fn dump_arena(seed: u64) {
+    let arena_store = components::ArenaStoreResource {
+        properties: HashMap::new(),
+        procedural: load_ron_asset(&["game", "procedural_arena.ron"]),
+    };
+    let arena = arena_store.get_properties(&components::ArenaNames::Procedural(seed));
+
+    match ron::ser::to_string_pretty(&arena, ron::ser::PrettyConfig::default()) {
+        Ok(arena_ron) => println!("// Procedural({})\nArena {}", seed, arena_ron),
+        Err(error) => eprintln!("Failed to write arena: {}", error),
+    }
+}
+
 
 fn main() -> amethyst::Result<()> {
     //amethyst::start_logger(Default::default());
     env_logger::init();
 
+    if std::env::args().any(|arg| arg == "--validate-arenas") {
+        std::process::exit(if validate_arenas() { 0 } else { 1 });
+    }
+
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(index) = args.iter().position(|arg| arg == "--dump-arena") {
+        match args.get(index + 1).and_then(|seed| seed.parse::<u64>().ok()) {
+            Some(seed) => dump_arena(seed),
+            None => eprintln!("Usage: --dump-arena <seed>"),
+        }
+        return Ok(());
+    }
+
     let app_root = application_root_dir()?;
 
     let resources = app_root.join("assets");
@@ -62,7 +124,7 @@ fn main() -> amethyst::Result<()> {
                 .with_plugin(RenderFlat2D::default()),
         )?;
 
-    let mut game = Application::new(resources, state::MyState::default(), game_data)?;
+    let mut game = Application::new(resources, lobby_state::LobbyState::default(), game_data)?;
     game.run();
 
     Ok(())
@@ -0,0 +1,20 @@
+use amethyst::{
+    ecs::prelude::World,
+    renderer::{SpriteRender},
+};
+
+#[derive(Clone)]
+pub struct DroneSpriteResource {
+    /// The render that locates the sprite in a sprite sheet resource
+    pub drone_body: SpriteRender,
+}
+
+pub fn initialize_drone_sprite_resource(
+    world: &mut World,
+    sprite_sheet_handle: &Vec<SpriteRender>,
+) -> () {
+    let resource = DroneSpriteResource {
+        drone_body: sprite_sheet_handle[5].clone(),
+    };
+    world.insert(resource.clone());
+}
@@ -1,7 +1,11 @@
 mod sprites_textures;
 mod weapon_fire_sprites;
 mod shield_power_sprites;
+mod drone_sprites;
+mod wall_sprites;
 
 pub use self::sprites_textures::{load_sprites, load_world_textures};
 pub use self::weapon_fire_sprites::{WeaponFireResource, initialize_weapon_fire_resource};
-pub use self::shield_power_sprites::{ShieldPowerResource, initialize_shield_power_resource};
\ No newline at end of file
+pub use self::shield_power_sprites::{ShieldPowerResource, initialize_shield_power_resource};
+pub use self::drone_sprites::{DroneSpriteResource, initialize_drone_sprite_resource};
+pub use self::wall_sprites::{WallSpriteResource, initialize_wall_sprite_resource, wall_sprite_index};
\ No newline at end of file
@@ -15,6 +15,21 @@ pub struct ShieldPowerResource {
     pub shield_360deg: SpriteRender,
 }
 
+impl ShieldPowerResource {
+    // The sprite for a step of SHIELD_COVERAGE_STEPS
+    pub fn get_sprite(&self, coverage_step: usize) -> SpriteRender {
+        match coverage_step {
+            0 => self.shield_off.clone(),
+            1 => self.shield_30deg.clone(),
+            2 => self.shield_60deg.clone(),
+            3 => self.shield_90deg.clone(),
+            4 => self.shield_180deg.clone(),
+            5 => self.shield_270deg.clone(),
+            _ => self.shield_360deg.clone(),
+        }
+    }
+}
+
 pub fn initialize_shield_power_resource(
     world: &mut World,
     sprite_sheet_handle: &Vec<SpriteRender>,
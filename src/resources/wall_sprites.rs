@@ -0,0 +1,45 @@
+use amethyst::{
+    ecs::prelude::World,
+    renderer::{SpriteRender},
+};
+
+use crate::components::{HitboxShape, WALL_DAMAGE_STATES};
+
+
+// Sprite sheet indices by damage state, intact first
+pub const RECTANGLE_WALL_SPRITES: [usize; WALL_DAMAGE_STATES] = [4, 20, 21];
+pub const CIRCLE_WALL_SPRITES: [usize; WALL_DAMAGE_STATES] = [5, 22, 23];
+pub const WALL_DEBRIS_SPRITE: usize = 24;
+
+pub fn wall_sprite_index(shape: HitboxShape, damage_state: usize) -> usize {
+    let damage_state = damage_state.min(WALL_DAMAGE_STATES - 1);
+
+    match shape {
+        HitboxShape::Rectangle => RECTANGLE_WALL_SPRITES[damage_state],
+        HitboxShape::Circle => CIRCLE_WALL_SPRITES[damage_state],
+    }
+}
+
+#[derive(Clone)]
+pub struct WallSpriteResource {
+    /// Every wall sprite in the sheet, picked by wall_sprite_index
+    pub sprites: Vec<SpriteRender>,
+    pub debris: SpriteRender,
+}
+
+impl WallSpriteResource {
+    pub fn get(&self, shape: HitboxShape, damage_state: usize) -> SpriteRender {
+        self.sprites[wall_sprite_index(shape, damage_state)].clone()
+    }
+}
+
+pub fn initialize_wall_sprite_resource(
+    world: &mut World,
+    sprite_sheet_handle: &Vec<SpriteRender>,
+) -> () {
+    let resource = WallSpriteResource {
+        sprites: sprite_sheet_handle.clone(),
+        debris: sprite_sheet_handle[WALL_DEBRIS_SPRITE].clone(),
+    };
+    world.insert(resource.clone());
+}
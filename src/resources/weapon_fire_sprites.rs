@@ -10,6 +10,7 @@ pub struct WeaponFireResource {
     pub player_2_weapon_fire: SpriteRender,
     pub player_3_weapon_fire: SpriteRender,
     pub player_4_weapon_fire: SpriteRender,
+    pub team_weapon_fire: SpriteRender, //white, tinted by team
 }
 
 pub fn initialize_weapon_fire_resource(
@@ -21,6 +22,7 @@ pub fn initialize_weapon_fire_resource(
         player_2_weapon_fire: sprite_sheet_handle[8].clone(),
         player_3_weapon_fire: sprite_sheet_handle[9].clone(),
         player_4_weapon_fire: sprite_sheet_handle[10].clone(),
+        team_weapon_fire: sprite_sheet_handle[19].clone(),
     };
     world.insert(resource.clone());
 }
@@ -6,31 +6,47 @@ use amethyst::{
         Anchor, FontHandle, LineMode, TtfFormat, UiImage, UiText,
         UiTransform,
     },
+    core::Time,
     ecs::prelude::{DispatcherBuilder, Dispatcher},
-    renderer::{Camera},
+    renderer::{Camera, resources::Tint, SpriteRender},
     utils::removal::Removal,
 };
 
 use crate::{components::{WeaponAimChild, WeaponFire}, entities::{
-    build_arena_store, intialize_arena, initialize_camera, intialize_player}, systems::PlayerSystemsSystem};
+    build_arena_store, build_game_mode_store, build_weapon_store, build_pickup_store, build_player_profiles, build_ship_store,
+    build_survival_store, reload_arena, ArenaFileWatcher,
+    intialize_arena, initialize_camera, intialize_player, intialize_pickups, intialize_flags, intialize_checkpoints,
+    intialize_safe_zone_edges, initialize_hud, initialize_kill_feed_hud, initialize_match_hud},
+    systems::PlayerSystemsSystem};
 use crate::components::{
-    ArenaNames, ArenaStoreResource, Arena, ArenaElement,
-    CameraOrthoEdges, 
-    Movable, Mass, Player, Hitbox, Weapon, Shield, ShieldAimChild};
+    ArenaNames, ArenaStoreResource, Arena, ArenaElement, ArenaFloor, MovingElement, DestructibleWall, Debris, Teleporter,
+    CameraOrthoEdges, GameModes, HillZone, Flag, FlagBase, GameModeStoreResource, MatchResource, RoundState, MAX_PLAYERS,
+    Movable, Mass, Player, Hitbox, Health, Weapon, Shield, ShieldAimChild, Pickup, PickupBoosts, StatusEffects, PowerBus, PowerPresets,
+    PlayerHud, MatchHud, KillFeedHud, Checkpoint, RaceProgress, Drone, SafeZoneEdge, CAMERA_BUFFER, CAMERA_BUFFER_RACE};
 use crate::systems::{
-    CameraTrackingSystem, 
+    ArenaMotionSystem, CameraTrackingSystem, DestructibleWallSystem,
     MovePlayerSystem, AimWeaponSystem, FireWeaponsSystem, MoveWeaponFireSystem,
-    HitboxCollisionDetection, HitboxImmovableCollisionDetection};
+    HitboxCollisionDetection, HitboxImmovableCollisionDetection, HitboxWeaponFireCollisionDetection,
+    PickupSystem, StatusEffectSystem, HudSystem, GameModeSystem, SuddenDeathSystem, TeleporterSystem,
+    add_game_mode_systems};
+use crate::lobby_state::LobbyState;
 use crate::resources::{
     load_sprites, load_world_textures, 
     initialize_weapon_fire_resource,
-    initialize_shield_power_resource};
+    initialize_shield_power_resource,
+    initialize_drone_sprite_resource,
+    initialize_wall_sprite_resource};
 
 
 #[derive(Default)]
 pub struct MyState<'a, 'b> {
     /// The `State` specific `Dispatcher`, containing `System`s only relevant for this `State`.
-    dispatcher: Option<Dispatcher<'a, 'b>>
+    dispatcher: Option<Dispatcher<'a, 'b>>,
+    /// Kept to rebuild the arena when its file changes.
+    arena_name: Option<ArenaNames>,
+    arena_watcher: ArenaFileWatcher,
+    sprites: Vec<SpriteRender>,
+    world_textures: Vec<SpriteRender>,
 }
 
 
@@ -50,36 +66,74 @@ impl<'a, 'b> SimpleState for MyState<'a, 'b> {
         let world = data.world;
 
         build_arena_store(world);
+        build_weapon_store(world);
+        build_pickup_store(world);
+        build_player_profiles(world);
+        build_ship_store(world);
+        build_survival_store(world);
 
         world.register::<Camera>();
         world.register::<CameraOrthoEdges>();
         world.register::<ArenaElement>();
+        world.register::<ArenaFloor>();
+        world.register::<MovingElement>();
+        world.register::<DestructibleWall>();
+        world.register::<Debris>();
+        world.register::<Teleporter>();
+        world.register::<HillZone>();
+        world.register::<Flag>();
+        world.register::<FlagBase>();
+        world.register::<Checkpoint>();
+        world.register::<RaceProgress>();
+        world.register::<Drone>();
+        world.register::<SafeZoneEdge>();
         world.register::<Player>();
         world.register::<Movable>();
         world.register::<Mass>();
         world.register::<Hitbox>();
+        world.register::<Health>();
+        world.register::<PowerBus>();
+        world.register::<PowerPresets>();
         world.register::<Shield>();
         world.register::<ShieldAimChild>();
         world.register::<Weapon>();
         world.register::<WeaponFire>();
         world.register::<WeaponAimChild>();
+        world.register::<Pickup>();
+        world.register::<PickupBoosts>();
+        world.register::<StatusEffects>();
+        world.register::<Tint>();
+        world.register::<PlayerHud>();
+        world.register::<MatchHud>();
+        world.register::<KillFeedHud>();
         world.register::<Removal<u32>>();
 
 
-        let arena_name = ArenaNames::StandardCombat;
+        // started without the lobby, play the default mode
+        if world.try_fetch::<MatchResource>().is_none() {
+            build_game_mode_store(world);
+
+            let game_mode = GameModes::DeathmatchKills;
+            let setup = world.read_resource::<GameModeStoreResource>().get_setup(&game_mode);
+            world.insert(MatchResource::new(game_mode, setup, MAX_PLAYERS));
+        }
+
+        let game_mode = world.read_resource::<MatchResource>().game_mode;
+        let sudden_death = world.read_resource::<MatchResource>().setup.sudden_death;
+        let arena_name;
         let arena_properties;
         {
             let fetched_arena_store = world.try_fetch::<ArenaStoreResource>();
 
             if let Some(arena_store) = fetched_arena_store {
-                arena_properties = match arena_store.properties.get(&arena_name) {
-                    Some(arena_props_get) => (*arena_props_get).clone(),
-                    _ => Arena::default(),
-                };
+                arena_name = world.read_resource::<MatchResource>().pick_arena(&arena_store);
+                arena_properties = arena_store.get_properties(&arena_name);
             } else {
+                arena_name = ArenaNames::StandardCombat;
                 arena_properties = Arena::default();
             }
         }
+        log::info!("{:?} on {:?}", game_mode, arena_name);
 
         // Place the camera
         initialize_camera(world, &arena_properties);
@@ -90,12 +144,36 @@ impl<'a, 'b> SimpleState for MyState<'a, 'b> {
 
         initialize_weapon_fire_resource(world, &sprites);
         initialize_shield_power_resource(world, &sprites);
+        initialize_drone_sprite_resource(world, &sprites);
+        initialize_wall_sprite_resource(world, &sprites);
 
-        intialize_arena(world, &arena_properties, &sprites, &world_textures);
-        intialize_player(world, &arena_properties, &sprites);
+        let spawn_points = {
+            let match_resource = world.read_resource::<MatchResource>();
+            let player_teams = if match_resource.setup.teams {
+                Some(&match_resource.player_teams[..])
+            } else {
+                None
+            };
+            arena_properties.get_spawn_points(player_teams)
+        };
+
+        let destroyed_walls = world.read_resource::<MatchResource>().get_destroyed_walls(&arena_name);
+        intialize_arena(world, &arena_properties, &sprites, &world_textures, &destroyed_walls);
+        intialize_player(world, &spawn_points, &sprites);
+        intialize_pickups(world, &arena_properties, &game_mode, &sprites);
+        intialize_flags(world, &arena_properties, &game_mode, &sprites);
+        intialize_checkpoints(world, &arena_properties, &game_mode, &sprites);
+        intialize_safe_zone_edges(world, &sudden_death, &sprites);
 
         create_ui_example(world);
+        initialize_hud(world, arena_properties.player_spawn_points.len());
+        initialize_match_hud(world);
+        initialize_kill_feed_hud(world);
+
 
+        self.arena_name = Some(arena_name);
+        self.sprites = sprites;
+        self.world_textures = world_textures;
 
         // Create the `DispatcherBuilder` and register some `System`s that should only run for this `State`.
         let mut dispatcher_builder = DispatcherBuilder::new();
@@ -103,7 +181,11 @@ impl<'a, 'b> SimpleState for MyState<'a, 'b> {
         dispatcher_builder.add(
             CameraTrackingSystem{
                 arena_name: arena_name,
-                arena_properties: arena_properties,
+                arena_properties: arena_properties.clone(),
+                arena_buffer: match game_mode {
+                    GameModes::Race => CAMERA_BUFFER_RACE,
+                    _ => CAMERA_BUFFER,
+                },
                 init_state: true},
             "camera_tracking_system",
             &[],
@@ -117,11 +199,36 @@ impl<'a, 'b> SimpleState for MyState<'a, 'b> {
         dispatcher_builder.add(
             HitboxCollisionDetection::default(), "hitbox_collision_system", &[]);
         dispatcher_builder.add(
-            HitboxImmovableCollisionDetection::default(), "hitbox_immovable_collision_system", &[]);
+            ArenaMotionSystem::default(), "arena_motion_system", &[]);
+        dispatcher_builder.add(
+            HitboxImmovableCollisionDetection::default(), "hitbox_immovable_collision_system",
+            &["arena_motion_system"]);
+        dispatcher_builder.add(
+            HitboxWeaponFireCollisionDetection::default(), "hitbox_weapon_fire_collision_system", &[]);
+        dispatcher_builder.add(
+            DestructibleWallSystem::new(arena_name), "destructible_wall_system", &[]);
+        dispatcher_builder.add(
+            TeleporterSystem::default(), "teleporter_system", &[]);
         dispatcher_builder.add(
             MoveWeaponFireSystem::default(), "move_weapon_fire_system", &[]);
         dispatcher_builder.add(
             PlayerSystemsSystem::default(), "player_systems_system", &[]);
+        dispatcher_builder.add(
+            PickupSystem::default(), "pickup_system", &[]);
+        dispatcher_builder.add(
+            StatusEffectSystem::default(), "status_effect_system", &[]);
+        dispatcher_builder.add(
+            HudSystem::default(), "hud_system", &[]);
+        dispatcher_builder.add(
+            GameModeSystem{
+                spawn_points: spawn_points.clone()},
+            "game_mode_system",
+            &[],
+        );
+        dispatcher_builder.add(
+            SuddenDeathSystem::new(&arena_properties), "sudden_death_system", &["game_mode_system"]);
+
+        add_game_mode_systems(&mut dispatcher_builder, &game_mode, &arena_properties);
 
         
         // Build and setup the `Dispatcher`.
@@ -131,6 +238,11 @@ impl<'a, 'b> SimpleState for MyState<'a, 'b> {
         self.dispatcher = Some(dispatcher);
     }
 
+    /// Everything belongs to the round, the next round or the lobby builds its own entities.
+    fn on_stop(&mut self, data: StateData<'_, GameData<'_, '_>>) {
+        data.world.delete_all();
+    }
+
     /// The following events are handled:
     /// - The game state is quit when either the close button is clicked or when the escape key is pressed.
     /// - Any other keypress is simply logged to the console.
@@ -155,7 +267,31 @@ impl<'a, 'b> SimpleState for MyState<'a, 'b> {
             dispatcher.dispatch(&data.world);
         }
 
-        Trans::None
+        // arena edits show up without a restart
+        let dt = data.world.read_resource::<Time>().delta_seconds();
+        if let (true, Some(arena_name)) = (self.arena_watcher.changed(dt), self.arena_name) {
+            reload_arena(data.world, &arena_name, &self.sprites, &self.world_textures);
+        }
+
+        // the round end freeze and intermission play out before moving on,
+        // then the next round starts over with fresh ships on their spawn points
+        if !data.world.read_resource::<MatchResource>().ready_for_next_round() {
+            return Trans::None;
+        }
+
+        let round_state = data.world.read_resource::<MatchResource>().state;
+        match round_state {
+            RoundState::RoundOver(winner) => {
+                log::info!("round won by {:?}", winner.map(|id| id + 1));
+                data.world.write_resource::<MatchResource>().next_round();
+                Trans::Switch(Box::new(MyState::default()))
+            },
+            RoundState::MatchOver(winner) => {
+                log::info!("match won by {:?}", winner.map(|id| id + 1));
+                Trans::Switch(Box::new(LobbyState::default()))
+            },
+            RoundState::Countdown | RoundState::Playing => Trans::None,
+        }
     }
 }
 
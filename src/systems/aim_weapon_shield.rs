@@ -6,7 +6,8 @@ use amethyst::{core::{Time, Transform, components::Parent}, derive::SystemDesc,
 use std::f32::consts::PI;
 use std::collections::HashMap;
 
-use crate::components::{AimControlState, Player, Shield, ShieldAimChild, Weapon, WeaponAimChild};
+use crate::components::{
+    calc_shield_coverage_step, AimControlState, PickupBoosts, Player, Shield, ShieldAimChild, Weapon, WeaponAimChild};
 use crate::resources::ShieldPowerResource;
 
 #[derive(SystemDesc, Default)]
@@ -21,6 +22,7 @@ impl<'s> System<'s> for AimWeaponSystem {
         WriteStorage<'s, WeaponAimChild>,
         WriteStorage<'s, Shield>,
         WriteStorage<'s, ShieldAimChild>,
+        ReadStorage<'s, PickupBoosts>,
         WriteStorage<'s, Transform>,
         WriteStorage<'s, SpriteRender>,
         ReadExpect<'s, ShieldPowerResource>,
@@ -41,6 +43,7 @@ impl<'s> System<'s> for AimWeaponSystem {
             mut weapon_aims,
             mut shields,
             mut shield_aims,
+            pickup_boosts,
             mut transforms,
             mut sprites,
             shield_power_resource,
@@ -56,11 +59,12 @@ impl<'s> System<'s> for AimWeaponSystem {
         let mut id_match_shield_angles: HashMap<u32, (f32, Option<f32>)> = HashMap::new();
         let mut id_match_shield_power_sprites: HashMap<u32, f32> = HashMap::new();
 
-        for (entity, player, weapon, shield, transform) in (
+        for (entity, player, weapon, shield, boosts, transform) in (
             &entities,
             &mut players,
             &mut weapons,
             &mut shields,
+            &pickup_boosts,
             &mut transforms,
         )
             .join()
@@ -172,7 +176,12 @@ impl<'s> System<'s> for AimWeaponSystem {
 
 
             
-            id_match_shield_power_sprites.insert(player_id, shield.power.get_power_pct());
+            if boosts.shield_overcharge_active() {
+                id_match_shield_power_sprites.insert(player_id, 3.0); //full coverage
+            }
+            else {
+                id_match_shield_power_sprites.insert(player_id, shield.power.get_power_pct());
+            }
             
         }
 
@@ -223,29 +232,8 @@ impl<'s> System<'s> for AimWeaponSystem {
 
             let shield_power = id_match_shield_power_sprites.get(&parent_id);
 
-            match shield_power {
-                Some(s) if *s < 0.333 => {
-                    *sprite = shield_power_resource.shield_off.clone();
-                },
-                Some(s) if *s < 0.666 => {
-                    *sprite = shield_power_resource.shield_30deg.clone();
-                },
-                Some(s) if *s < 1.0 => {
-                    *sprite = shield_power_resource.shield_60deg.clone();
-                },
-                Some(s) if *s < 1.666 => {
-                    *sprite = shield_power_resource.shield_90deg.clone();
-                },
-                Some(s) if *s < 2.333 => {
-                    *sprite = shield_power_resource.shield_180deg.clone();
-                },
-                Some(s) if *s < 3.0 => {
-                    *sprite = shield_power_resource.shield_270deg.clone();
-                },
-                Some(s) if *s >= 3.0 => {
-                    *sprite = shield_power_resource.shield_360deg.clone();
-                },
-                _ => {}
+            if let Some(shield_power) = shield_power {
+                *sprite = shield_power_resource.get_sprite(calc_shield_coverage_step(*shield_power));
             }
         }
     }
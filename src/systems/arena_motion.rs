@@ -0,0 +1,63 @@
+use amethyst::{
+    core::{Time, Transform},
+    derive::SystemDesc,
+    ecs::{
+        Join, Read, ReadExpect, System, SystemData, World,
+        WriteStorage,
+    },
+};
+
+use crate::components::{ArenaElement, MatchResource, MovingElement};
+
+
+// Runs the motion scripts of moving arena elements, sweeping bars and sliding doors.
+// Elements hold still while the round is frozen, like the ships.
+#[derive(SystemDesc, Default)]
+pub struct ArenaMotionSystem {}
+
+impl<'s> System<'s> for ArenaMotionSystem {
+    type SystemData = (
+        WriteStorage<'s, MovingElement>,
+        WriteStorage<'s, ArenaElement>,
+        WriteStorage<'s, Transform>,
+        ReadExpect<'s, MatchResource>,
+        Read<'s, Time>,
+    );
+
+    fn setup(&mut self, _world: &mut World) {
+    }
+
+    fn run(
+        &mut self,
+        (
+            mut moving_elements,
+            mut arena_elements,
+            mut transforms,
+            match_resource,
+            time,
+        ): Self::SystemData,
+    ) {
+        let dt = if match_resource.frozen() {
+            0.0
+        } else {
+            time.delta_seconds()
+        };
+
+        for (moving_element, arena_element, transform) in (
+            &mut moving_elements,
+            &mut arena_elements,
+            &mut transforms,
+        )
+            .join()
+        {
+            let (x, y, rotation) = moving_element.advance(dt);
+
+            transform.set_translation_x(x);
+            transform.set_translation_y(y);
+            transform.set_rotation_2d(rotation.to_radians());
+
+            arena_element.x = x;
+            arena_element.y = y;
+        }
+    }
+}
@@ -17,6 +17,7 @@ const CAMERA_TRANSLATE_MAX_RATE: f32 = 100.0;
 pub struct CameraTrackingSystem {
     pub arena_name: ArenaNames,
     pub arena_properties: Arena,
+    pub arena_buffer: f32,
     pub init_state: bool,
 }
 
@@ -29,6 +30,7 @@ impl<'s> System<'s> for CameraTrackingSystem {
         WriteStorage<'s, Camera>,
         WriteStorage<'s, CameraOrthoEdges>,
         ReadExpect<'s, ScreenDimensions>,
+        ReadExpect<'s, ArenaStoreResource>,
     );
 
     fn setup(&mut self, world: &mut World) {
@@ -37,10 +39,7 @@ impl<'s> System<'s> for CameraTrackingSystem {
         {
             let fetched_arena_store = world.try_fetch::<ArenaStoreResource>();
             if let Some(arena_store) = fetched_arena_store {
-                self.arena_properties = match arena_store.properties.get(&self.arena_name) {
-                    Some(arena_props_get) => (*arena_props_get).clone(),
-                    _ => Arena::default(),
-                };
+                self.arena_properties = arena_store.get_properties(&self.arena_name);
             } else {
                 self.arena_properties = Arena::default();
             }
@@ -57,10 +56,19 @@ impl<'s> System<'s> for CameraTrackingSystem {
         mut cameras,
         mut camera_orthos,
         screen_dimensions,
+        arena_store,
     ): Self::SystemData,
     ) {
         let dt = time.delta_seconds();
 
+        // the arena file can be reloaded mid round, start over on the new bounds
+        if let Some(arena_props_get) = arena_store.properties.get(&self.arena_name) {
+            if arena_props_get.width != self.arena_properties.width || arena_props_get.height != self.arena_properties.height {
+                self.arena_properties = (*arena_props_get).clone();
+                self.init_state = true;
+            }
+        }
+
         let mut player_bounds = CameraPlayerBounds::new();
 
         for (player, movable, transform) in (&players, &movables, &transforms).join() {
@@ -76,7 +84,8 @@ impl<'s> System<'s> for CameraTrackingSystem {
 
         player_bounds.calc_bounds_on_players_and_arena(
             self.arena_properties.width,
-            self.arena_properties.height
+            self.arena_properties.height,
+            self.arena_buffer,
         );
 
         for (camera, camera_ortho, transform) in (&mut cameras, &mut camera_orthos, &mut transforms).join() {
@@ -0,0 +1,214 @@
+use amethyst::{
+    core::{Time, Transform},
+    derive::SystemDesc,
+    ecs::{Join, Read, ReadStorage, System, SystemData, World, WriteExpect, WriteStorage},
+};
+
+extern crate nalgebra as na;
+use na::Isometry2;
+use ncollide2d::{
+    query::{self, Proximity},
+    shape::{Ball, ShapeHandle},
+};
+
+use crate::components::{
+    get_fixed_shape_pos, get_leaders, get_movable_shape_pos, Flag, FlagBase, FlagStates, Hitbox, Mass,
+    MatchResource, Player, FLAG_CARRIER_MASS};
+
+
+struct FlagPlayer {
+    id: usize,
+    team: usize,
+    active: bool,
+    x: f32,
+    y: f32,
+    pos: Isometry2<f32>,
+    shape: Ball<f32>,
+}
+
+// CaptureTheFlag: carry the enemy flag to your own base while your flag is home.
+// Touching your own dropped flag returns it, and a carrier drops the flag when destroyed.
+#[derive(SystemDesc, Default)]
+pub struct CaptureTheFlagSystem {
+}
+
+impl<'s> System<'s> for CaptureTheFlagSystem {
+    type SystemData = (
+        ReadStorage<'s, Player>,
+        ReadStorage<'s, Hitbox>,
+        WriteStorage<'s, Transform>,
+        WriteStorage<'s, Flag>,
+        ReadStorage<'s, FlagBase>,
+        WriteStorage<'s, Mass>,
+        WriteExpect<'s, MatchResource>,
+        Read<'s, Time>,
+    );
+
+    fn setup(&mut self, _world: &mut World) {
+    }
+
+    fn run(
+        &mut self,
+        (
+            players,
+            hitboxes,
+            mut transforms,
+            mut flags,
+            flag_bases,
+            mut masses,
+            mut match_resource,
+            time,
+        ): Self::SystemData,
+    ) {
+        if !match_resource.round_active() {
+            return;
+        }
+
+        let dt = time.delta_seconds();
+
+        let flag_players: Vec<FlagPlayer> = (&players, &hitboxes, &transforms)
+            .join()
+            .map(|(player, hitbox, transform)| {
+                let (pos, shape) = get_movable_shape_pos(transform, hitbox);
+                FlagPlayer {
+                    id: player.id,
+                    team: player.team,
+                    active: player.is_active(),
+                    x: transform.translation().x,
+                    y: transform.translation().y,
+                    pos,
+                    shape,
+                }
+            })
+            .collect();
+
+        let bases: Vec<(usize, Isometry2<f32>, ShapeHandle<f32>)> = (&flag_bases, &hitboxes, &transforms)
+            .join()
+            .map(|(flag_base, hitbox, transform)| {
+                let (pos, shape) = get_fixed_shape_pos(transform, hitbox);
+                (flag_base.team, pos, shape)
+            })
+            .collect();
+
+        let teams_at_base: Vec<usize> = (&flags)
+            .join()
+            .filter(|flag| flag.get_state() == FlagStates::AtBase)
+            .map(|flag| flag.team)
+            .collect();
+
+        let mut carriers: Vec<usize> = (&flags).join().filter_map(|flag| flag.carrier()).collect();
+
+        let mut mass_changes: Vec<(usize, f32)> = vec![];
+        let mut captures: Vec<usize> = vec![];
+
+        for (flag, flag_hitbox, flag_transform) in (&mut flags, &hitboxes, &mut transforms).join() {
+            match flag.get_state() {
+                FlagStates::Carried(carrier_id) => {
+                    let carrier = flag_players.iter().find(|flag_player| flag_player.id == carrier_id);
+
+                    match carrier {
+                        Some(carrier) if carrier.active => {
+                            flag_transform.set_translation_x(carrier.x);
+                            flag_transform.set_translation_y(carrier.y);
+
+                            let at_own_base = bases.iter().any(|(base_team, base_pos, base_shape)| {
+                                *base_team == carrier.team && query::proximity(
+                                    base_pos,
+                                    &**base_shape,
+                                    &carrier.pos,
+                                    &carrier.shape,
+                                    0.0,
+                                ) == Proximity::Intersecting
+                            });
+
+                            // the carrier's own flag has to be home to score
+                            if at_own_base && teams_at_base.contains(&carrier.team) {
+                                log::info!("p{} captured team {} flag", carrier.id + 1, flag.team + 1);
+
+                                flag.return_to_base();
+                                flag_transform.set_translation_x(flag.base_x);
+                                flag_transform.set_translation_y(flag.base_y);
+                                mass_changes.push((carrier.id, -FLAG_CARRIER_MASS));
+                                captures.push(carrier.id);
+                            }
+                        },
+                        _ => {
+                            // left where the carrier was destroyed
+                            log::info!("p{} dropped team {} flag", carrier_id + 1, flag.team + 1);
+
+                            flag.set_dropped();
+                            mass_changes.push((carrier_id, -FLAG_CARRIER_MASS));
+                        },
+                    }
+                },
+                flag_state => {
+                    if flag_state == FlagStates::Dropped {
+                        flag.return_cooldown.timer_update(&dt);
+
+                        if flag.return_cooldown.timer_active() {
+                            flag.return_to_base();
+                            flag_transform.set_translation_x(flag.base_x);
+                            flag_transform.set_translation_y(flag.base_y);
+                            continue;
+                        }
+                    }
+
+                    let (flag_pos, flag_shape) = get_movable_shape_pos(flag_transform, flag_hitbox);
+
+                    let toucher = flag_players.iter().find(|flag_player| {
+                        flag_player.active && query::proximity(
+                            &flag_pos,
+                            &flag_shape,
+                            &flag_player.pos,
+                            &flag_player.shape,
+                            0.0,
+                        ) == Proximity::Intersecting
+                    });
+
+                    if let Some(toucher) = toucher {
+                        if toucher.team != flag.team && !carriers.contains(&toucher.id) {
+                            log::info!("p{} took team {} flag", toucher.id + 1, flag.team + 1);
+
+                            flag.pickup(toucher.id);
+                            carriers.push(toucher.id);
+                            mass_changes.push((toucher.id, FLAG_CARRIER_MASS));
+                        }
+                        else if toucher.team == flag.team && flag_state == FlagStates::Dropped {
+                            log::info!("p{} returned team {} flag", toucher.id + 1, flag.team + 1);
+
+                            flag.return_to_base();
+                            flag_transform.set_translation_x(flag.base_x);
+                            flag_transform.set_translation_y(flag.base_y);
+                        }
+                    }
+                },
+            }
+        }
+
+        for (player, mass) in (&players, &mut masses).join() {
+            for (player_id, mass_change) in mass_changes.iter() {
+                if player.id == *player_id {
+                    mass.mass += mass_change;
+                }
+            }
+        }
+
+        for player_id in captures.iter() {
+            if let Some(score) = match_resource.get_score_mut(*player_id) {
+                score.score += 1.0;
+            }
+        }
+
+        let team_scores = match_resource.team_scores(|score| score.score as i32);
+
+        if let Some(score_target) = match_resource.setup.score_target {
+            let leaders = get_leaders(&team_scores);
+            if leaders.len() == 1 && team_scores[leaders[0]] as f32 >= score_target {
+                match_resource.end_round(Some(leaders[0]));
+                return;
+            }
+        }
+
+        match_resource.check_time_limit(&team_scores);
+    }
+}
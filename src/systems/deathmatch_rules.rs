@@ -0,0 +1,57 @@
+use amethyst::{
+    derive::SystemDesc,
+    ecs::{System, SystemData, World, WriteExpect},
+};
+
+use crate::components::{get_leaders, MatchResource};
+
+
+// DeathmatchKills: first to the kill target wins,
+// otherwise the most kills when the time limit runs out. Teams share their kills.
+#[derive(SystemDesc, Default)]
+pub struct KillsRulesSystem {
+}
+
+impl<'s> System<'s> for KillsRulesSystem {
+    type SystemData = WriteExpect<'s, MatchResource>;
+
+    fn setup(&mut self, _world: &mut World) {
+    }
+
+    fn run(&mut self, mut match_resource: Self::SystemData) {
+        if !match_resource.round_active() {
+            return;
+        }
+
+        let scores: Vec<i32> = match_resource.team_scores(|score| score.kill_score());
+
+        if let Some(kill_target) = match_resource.setup.kill_target {
+            let leaders = get_leaders(&scores);
+            if leaders.len() == 1 && scores[leaders[0]] >= kill_target as i32 {
+                match_resource.end_round(Some(leaders[0]));
+                return;
+            }
+        }
+
+        match_resource.check_time_limit(&scores);
+    }
+}
+
+
+// DeathmatchTimedKD: the best kills minus deaths when the time limit runs out
+#[derive(SystemDesc, Default)]
+pub struct TimedKDRulesSystem {
+}
+
+impl<'s> System<'s> for TimedKDRulesSystem {
+    type SystemData = WriteExpect<'s, MatchResource>;
+
+    fn setup(&mut self, _world: &mut World) {
+    }
+
+    fn run(&mut self, mut match_resource: Self::SystemData) {
+        let scores: Vec<i32> = match_resource.team_scores(|score| score.kd_score());
+
+        match_resource.check_time_limit(&scores);
+    }
+}
@@ -0,0 +1,195 @@
+use amethyst::{
+    core::{Time, Transform},
+    derive::SystemDesc,
+    ecs::{
+        Entities, Entity, Join, LazyUpdate, Read, ReadExpect, ReadStorage, System, SystemData, World,
+        WriteExpect, WriteStorage,
+    },
+    renderer::{palette::Srgba, resources::Tint, SpriteRender, Transparent},
+};
+
+extern crate nalgebra as na;
+use na::{Isometry2};
+use ncollide2d::query::{self, Proximity};
+use ncollide2d::shape::ShapeHandle;
+
+use std::f32::consts::PI;
+
+use crate::components::{
+    get_fixed_shape_pos, get_movable_shape_pos, get_moving_shape_pos, ArenaNames, Debris, DestructibleWall, Hitbox,
+    MatchResource, Movable, MovingElement, WeaponFire};
+use crate::entities::{spawn_wall_debris, RUBBLE_TINT};
+use crate::resources::WallSpriteResource;
+
+
+const DEBRIS_PER_HIT: usize = 2;
+const DEBRIS_ON_DESTROY: usize = 8;
+const DEBRIS_SPEED: f32 = 80.0;
+
+
+// Weapon fire chips away at walls with hit points, walls without still let shots through.
+// Walls show their damage through sprite swaps, throw debris when hit,
+// and fall to rubble without a collider at zero.
+#[derive(SystemDesc)]
+pub struct DestructibleWallSystem {
+    arena_name: ArenaNames, //to keep walls down in later rounds
+}
+
+impl DestructibleWallSystem {
+    pub fn new(arena_name: ArenaNames) -> DestructibleWallSystem {
+        DestructibleWallSystem {arena_name}
+    }
+}
+
+impl<'s> System<'s> for DestructibleWallSystem {
+    type SystemData = (
+        Entities<'s>,
+        WriteStorage<'s, DestructibleWall>,
+        WriteStorage<'s, Debris>,
+        ReadStorage<'s, WeaponFire>,
+        ReadStorage<'s, Movable>,
+        ReadStorage<'s, Hitbox>,
+        ReadStorage<'s, MovingElement>,
+        WriteStorage<'s, Transform>,
+        WriteStorage<'s, SpriteRender>,
+        ReadExpect<'s, WallSpriteResource>,
+        ReadExpect<'s, LazyUpdate>,
+        WriteExpect<'s, MatchResource>,
+        Read<'s, Time>,
+    );
+
+    fn setup(&mut self, _world: &mut World) {
+    }
+
+    fn run(
+        &mut self,
+        (
+            entities,
+            mut destructible_walls,
+            mut debris,
+            weapon_fires,
+            movables,
+            hitboxes,
+            moving_elements,
+            mut transforms,
+            mut sprite_renders,
+            wall_sprites,
+            lazy_update,
+            mut match_resource,
+            time,
+        ): Self::SystemData,
+    ) {
+        let dt = time.delta_seconds();
+
+        for (entity, debris, transform) in (&entities, &mut debris, &mut transforms).join() {
+            transform.prepend_translation_x(debris.dx * dt);
+            transform.prepend_translation_y(debris.dy * dt);
+            transform.rotate_2d(debris.spin * dt);
+
+            debris.lifetime -= dt;
+            if debris.lifetime <= 0.0 {
+                let _ = entities.delete(entity);
+            }
+        }
+
+        // standing walls only, rubble has lost its hitbox
+        let walls: Vec<(Entity, Isometry2<f32>, ShapeHandle<f32>)> = (
+            &entities,
+            &destructible_walls,
+            &hitboxes,
+            &transforms,
+            (&moving_elements).maybe(),
+        )
+            .join()
+            .map(|(entity, _, hitbox, transform, moving_element)| {
+                let (pos, shape) = match moving_element {
+                    Some(moving_element) => get_moving_shape_pos(
+                        transform, hitbox, moving_element.get_origin_rotation()),
+                    None => get_fixed_shape_pos(transform, hitbox),
+                };
+                (entity, pos, shape)
+            })
+            .collect();
+
+        // wall, damage, and where the shot hit from
+        let mut hits: Vec<(Entity, f32, f32, f32, f32, f32)> = Vec::new();
+
+        for (weapon_fire_entity, weapon_fire, movable, weapon_fire_hitbox, weapon_fire_transform) in (
+            &entities,
+            &weapon_fires,
+            &movables,
+            &hitboxes,
+            &transforms,
+        )
+            .join()
+        {
+            let (fire_pos, fire_shape) = get_movable_shape_pos(weapon_fire_transform, weapon_fire_hitbox);
+
+            for (wall_entity, wall_pos, wall_shape) in walls.iter() {
+                let proximity = query::proximity(&fire_pos, &fire_shape, wall_pos, &**wall_shape, 0.0);
+
+                if proximity == Proximity::Intersecting {
+                    let damage = match_resource.scale_damage(weapon_fire.damage, weapon_fire.owner_id, None);
+
+                    hits.push((
+                        *wall_entity,
+                        damage,
+                        weapon_fire_transform.translation().x,
+                        weapon_fire_transform.translation().y,
+                        movable.dx,
+                        movable.dy,
+                    ));
+
+                    let _ = entities.delete(weapon_fire_entity);
+                    break;
+                }
+            }
+        }
+
+        for (wall_entity, damage, hit_x, hit_y, shot_dx, shot_dy) in hits {
+            let (destructible_wall, hitbox) = match (destructible_walls.get_mut(wall_entity), hitboxes.get(wall_entity)) {
+                (Some(destructible_wall), Some(hitbox)) => (destructible_wall, hitbox),
+                _ => continue,
+            };
+            if !destructible_wall.health.is_alive() {
+                continue; //already brought down by an earlier shot this frame
+            }
+
+            let damage_state = destructible_wall.damage_state();
+            destructible_wall.health.damage(damage);
+
+            // chips fly back toward the shooter
+            let back_angle = (-shot_dy).atan2(-shot_dx);
+            for chip in 0..DEBRIS_PER_HIT {
+                let angle = back_angle + (chip as f32 - 0.5) * 0.8;
+                spawn_wall_debris(&entities, &lazy_update, &wall_sprites.debris,
+                    hit_x, hit_y, DEBRIS_SPEED * angle.cos(), DEBRIS_SPEED * angle.sin());
+            }
+
+            if destructible_wall.damage_state() != damage_state {
+                let _ = sprite_renders.insert(
+                    wall_entity, wall_sprites.get(hitbox.props.shape, destructible_wall.damage_state()));
+            }
+
+            if !destructible_wall.health.is_alive() {
+                if let Some(wall_transform) = transforms.get(wall_entity) {
+                    let (x, y) = (wall_transform.translation().x, wall_transform.translation().y);
+
+                    for chip in 0..DEBRIS_ON_DESTROY {
+                        let angle = chip as f32 / DEBRIS_ON_DESTROY as f32 * 2.0 * PI;
+                        spawn_wall_debris(&entities, &lazy_update, &wall_sprites.debris,
+                            x, y, DEBRIS_SPEED * angle.cos(), DEBRIS_SPEED * angle.sin());
+                    }
+                }
+
+                let [r, g, b, a] = RUBBLE_TINT;
+                lazy_update.remove::<Hitbox>(wall_entity);
+                lazy_update.insert(wall_entity, Tint(Srgba::new(r, g, b, a)));
+                lazy_update.insert(wall_entity, Transparent);
+
+                match_resource.destroy_wall(self.arena_name, destructible_wall.index);
+                log::info!("wall arena_elements[{}] destroyed", destructible_wall.index);
+            }
+        }
+    }
+}
@@ -0,0 +1,194 @@
+use amethyst::{
+    core::{Time, Transform},
+    derive::SystemDesc,
+    ecs::{
+        Entities, Join, LazyUpdate, Read, ReadExpect, ReadStorage, System, SystemData, World,
+        WriteExpect, WriteStorage,
+    },
+};
+
+use ncollide2d::query::{self, Proximity};
+
+use crate::components::{
+    get_movable_shape_pos, Drone, Health, Hitbox, Mass, MatchResource, Movable, Player, Weapon};
+use crate::entities::fire_weapon;
+use crate::resources::WeaponFireResource;
+
+
+const DRONE_FIRE_RANGE_MULT: f32 = 1.5; //starts firing a little before it's in position
+
+
+// Drone AI: each drone chases the nearest active player.
+// Armed drones hold at their fire range and shoot, unarmed drones ram for contact damage.
+// Destroyed drones are removed and credited to the last player to hit them.
+#[derive(SystemDesc, Default)]
+pub struct DroneSystem {
+}
+
+impl<'s> System<'s> for DroneSystem {
+    type SystemData = (
+        Entities<'s>,
+        ReadStorage<'s, Drone>,
+        WriteStorage<'s, Movable>,
+        ReadStorage<'s, Mass>,
+        ReadStorage<'s, Hitbox>,
+        WriteStorage<'s, Transform>,
+        WriteStorage<'s, Weapon>,
+        WriteStorage<'s, Health>,
+        WriteStorage<'s, Player>,
+        WriteExpect<'s, MatchResource>,
+        ReadExpect<'s, WeaponFireResource>,
+        ReadExpect<'s, LazyUpdate>,
+        Read<'s, Time>,
+    );
+
+    fn setup(&mut self, _world: &mut World) {
+    }
+
+    fn run(
+        &mut self,
+        (
+            entities,
+            drones,
+            mut movables,
+            masses,
+            hitboxes,
+            mut transforms,
+            mut weapons,
+            mut healths,
+            mut players,
+            mut match_resource,
+            weapon_fire_resource,
+            lazy_update,
+            time,
+        ): Self::SystemData,
+    ) {
+        if !match_resource.round_active() {
+            return;
+        }
+
+        let dt = time.delta_seconds();
+
+        let targets: Vec<_> = (&players, &hitboxes, &transforms)
+            .join()
+            .filter(|(player, _, _)| player.is_active())
+            .map(|(player, hitbox, transform)| {
+                let (pos, shape) = get_movable_shape_pos(transform, hitbox);
+                (player.id, transform.translation().x, transform.translation().y, pos, shape)
+            })
+            .collect();
+
+        let mut ram_hits: Vec<(usize, f32)> = vec![];
+
+        for (entity, drone, movable, mass, hitbox, transform, health, weapon) in (
+            &entities,
+            &drones,
+            &mut movables,
+            &masses,
+            &hitboxes,
+            &mut transforms,
+            &healths,
+            (&mut weapons).maybe(),
+        )
+            .join()
+        {
+            if !health.is_alive() {
+                match drone.kill_credit() {
+                    Some(player_id) => {
+                        log::info!("p{} destroyed a {:?} drone", player_id + 1, drone.kind);
+                        if let Some(score) = match_resource.get_score_mut(player_id) {
+                            score.kills += 1;
+                        }
+                    },
+                    None => log::info!("{:?} drone destroyed", drone.kind),
+                }
+
+                let _ = entities.delete(entity);
+                continue;
+            }
+
+            let drone_x = transform.translation().x;
+            let drone_y = transform.translation().y;
+
+            let nearest = targets.iter().min_by(|a, b| {
+                let a_dist = (a.1 - drone_x).powi(2) + (a.2 - drone_y).powi(2);
+                let b_dist = (b.1 - drone_x).powi(2) + (b.2 - drone_y).powi(2);
+                a_dist.partial_cmp(&b_dist).unwrap_or(std::cmp::Ordering::Equal)
+            });
+
+            let accel = movable.max_accel_force / mass.mass * dt;
+
+            if let Some((target_id, target_x, target_y, target_pos, target_shape)) = nearest {
+                let offset_x = target_x - drone_x;
+                let offset_y = target_y - drone_y;
+                let dist = (offset_x.powi(2) + offset_y.powi(2)).sqrt().max(0.001);
+
+                // the same facing as the player ships, so weapon fire heads the right way
+                let angle = (-offset_x).atan2(offset_y);
+                transform.set_rotation_2d(angle);
+
+                let approach = weapon.is_none() || dist > drone.fire_range;
+                let thrust = if approach { accel } else { -accel };
+                movable.dx += thrust * offset_x / dist;
+                movable.dy += thrust * offset_y / dist;
+
+                if let Some(weapon) = weapon {
+                    weapon.angle = angle;
+                    weapon.cooldown.timer_update(&dt);
+
+                    if dist <= drone.fire_range * DRONE_FIRE_RANGE_MULT && weapon.cooldown.timer_active() {
+                        fire_weapon(
+                            &entities,
+                            entity.id(),
+                            None,
+                            transform,
+                            weapon,
+                            &weapon_fire_resource,
+                            &lazy_update,
+                        );
+                        weapon.cooldown.timer_reset();
+                    }
+                }
+
+                if drone.ram_damage > 0.0 {
+                    let (drone_pos, drone_shape) = get_movable_shape_pos(transform, hitbox);
+
+                    if query::proximity(
+                        &drone_pos,
+                        &drone_shape,
+                        target_pos,
+                        target_shape,
+                        0.0,
+                    ) == Proximity::Intersecting {
+                        ram_hits.push((*target_id, drone.ram_damage * dt));
+                    }
+                }
+            }
+
+            // slows to a stop with nobody left to chase
+            let speed = (movable.dx.powi(2) + movable.dy.powi(2)).sqrt();
+            let max_speed = if nearest.is_some() {
+                drone.max_speed
+            } else {
+                (speed - accel).max(0.0)
+            };
+
+            if speed > max_speed {
+                movable.dx *= max_speed / speed;
+                movable.dy *= max_speed / speed;
+            }
+
+            transform.prepend_translation_x(movable.dx * dt);
+            transform.prepend_translation_y(movable.dy * dt);
+        }
+
+        for (player, health) in (&mut players, &mut healths).join() {
+            for (player_id, damage) in ram_hits.iter() {
+                if player.id == *player_id {
+                    // no kill credit, a drone kill is an environment death
+                    health.damage(match_resource.scale_damage(*damage, None, Some(player.id)));
+                }
+            }
+        }
+    }
+}
@@ -6,9 +6,9 @@ use amethyst::{
     input::{InputHandler, StringBindings},
 };
 
-use crate::components::{Player, Weapon};
+use crate::components::{MatchResource, Player, Weapon};
 use crate::resources::WeaponFireResource;
-use crate::entities::{fire_weapon};
+use crate::entities::{fire_weapon, get_team_tint};
 
 #[derive(SystemDesc, Default)]
 pub struct FireWeaponsSystem;
@@ -23,6 +23,7 @@ impl<'s> System<'s> for FireWeaponsSystem {
         ReadExpect<'s, LazyUpdate>,
         Read<'s, Time>,
         Read<'s, InputHandler<StringBindings>>,
+        ReadExpect<'s, MatchResource>,
     );
 
     fn run(
@@ -36,8 +37,13 @@ impl<'s> System<'s> for FireWeaponsSystem {
             lazy_update,
             time,
             input,
+            match_resource,
         ): Self::SystemData,
     ) {
+        if match_resource.frozen() {
+            return;
+        }
+
         let dt = time.delta_seconds();
 
         for (entity, player, weapon, transform) in (
@@ -48,6 +54,10 @@ impl<'s> System<'s> for FireWeaponsSystem {
         )
             .join()
         {
+            if !player.is_active() || weapon.holstered {
+                continue;
+            }
+
             let primary_fire = match player.id {
                 0 => input.axis_value("p1_fire"),
                 1 => input.axis_value("p2_fire"),
@@ -57,23 +67,29 @@ impl<'s> System<'s> for FireWeaponsSystem {
             };
 
             match (primary_fire, weapon.cooldown.timer_active(), weapon.power.is_powered()) {
-                (_, _, power) if (power  == true) => {
+                (_, _, power) if (power == false) => {
                     //Do nothing, not even decrease cooldown timer, weapon systems are off
                 }
-                (_, cooldown_active, _) if (cooldown_active == true) => {
+                (_, cooldown_ready, _) if (cooldown_ready == false) => {
                     weapon.cooldown.timer_update(&dt);
                 }
-                (Some(fire), cooldown_active, _) if (fire > 0.5) & (cooldown_active == false) => {
-                    fire_weapon(
+                (Some(fire), cooldown_ready, _) if (fire > 0.5) & (cooldown_ready == true) => {
+                    let weapon_fire_entity = fire_weapon(
                         &entities,
                         entity.id(),
-                        player.id,
+                        Some(player.id),
                         &transform,
                         &weapon,
                         &weapon_fire_resource,
                         &lazy_update,
                     );
 
+                    // team shots are colored by team instead of by player
+                    if match_resource.setup.teams {
+                        lazy_update.insert(weapon_fire_entity, weapon_fire_resource.team_weapon_fire.clone());
+                        lazy_update.insert(weapon_fire_entity, get_team_tint(player.team));
+                    }
+
                     weapon.cooldown.timer_reset_multiplier(1.0 / weapon.power.get_power_pct());
                 }
                 (_, _, _) => {}
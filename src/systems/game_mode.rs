@@ -0,0 +1,193 @@
+use amethyst::{
+    core::{HiddenPropagate, Time, Transform},
+    derive::SystemDesc,
+    ecs::{
+        DispatcherBuilder, Entities, Join, Read, System, SystemData, World,
+        WriteExpect, WriteStorage,
+    },
+};
+
+use std::f32::consts::PI;
+
+use crate::systems::{
+    CaptureTheFlagSystem, DroneSystem, GunGameSystem, KillsRulesSystem, KingOfTheHillSystem, RaceSystem, StockRulesSystem,
+    SurvivalSystem, TimedKDRulesSystem};
+
+use crate::components::{
+    Arena, GameModes, Health, MatchResource, Movable, PickupBoosts, Player, PlayerSpawnPoint, PlayerState,
+    Shield, StatusEffects, Weapon};
+
+
+// Each mode adds the systems for its own rules, win conditions and scoring.
+// The GameModeSystem below runs for every mode.
+pub fn add_game_mode_systems<'a, 'b>(
+    dispatcher_builder: &mut DispatcherBuilder<'a, 'b>,
+    game_mode: &GameModes,
+    arena_properties: &Arena,
+) {
+    match game_mode {
+        GameModes::ClassicGunGame => {
+            dispatcher_builder.add(
+                GunGameSystem::default(), "gun_game_system", &["game_mode_system"]);
+        },
+        GameModes::DeathmatchKills => {
+            dispatcher_builder.add(
+                KillsRulesSystem::default(), "kills_rules_system", &["game_mode_system"]);
+        },
+        GameModes::DeathmatchStock => {
+            dispatcher_builder.add(
+                StockRulesSystem::default(), "stock_rules_system", &["game_mode_system"]);
+        },
+        GameModes::DeathmatchTimedKD => {
+            dispatcher_builder.add(
+                TimedKDRulesSystem::default(), "timed_kd_rules_system", &["game_mode_system"]);
+        },
+        GameModes::KingOfTheHill => {
+            dispatcher_builder.add(
+                KingOfTheHillSystem::default(), "king_of_the_hill_system", &["game_mode_system"]);
+        },
+        GameModes::CaptureTheFlag => {
+            dispatcher_builder.add(
+                CaptureTheFlagSystem::default(), "capture_the_flag_system", &["game_mode_system"]);
+        },
+        GameModes::Race => {
+            dispatcher_builder.add(
+                RaceSystem::default(), "race_system", &["game_mode_system"]);
+        },
+        GameModes::SurvivalWaves => {
+            dispatcher_builder.add(
+                SurvivalSystem::new(arena_properties), "survival_system", &["game_mode_system"]);
+            dispatcher_builder.add(
+                DroneSystem::default(), "drone_system", &["survival_system"]);
+        },
+    }
+}
+
+
+// Runs the start countdown, round clock and round end timer, takes destroyed players out of play and credits their deaths,
+// then respawns them at their spawn point after the mode's respawn time.
+// Players out of lives stay eliminated, and the camera stops tracking them.
+// In modes with revives, destroyed players are downed in place instead.
+#[derive(SystemDesc)]
+pub struct GameModeSystem {
+    pub spawn_points: Vec<PlayerSpawnPoint>,
+}
+
+impl<'s> System<'s> for GameModeSystem {
+    type SystemData = (
+        Entities<'s>,
+        WriteStorage<'s, Player>,
+        WriteStorage<'s, Health>,
+        WriteStorage<'s, Transform>,
+        WriteStorage<'s, Movable>,
+        WriteStorage<'s, Shield>,
+        WriteStorage<'s, Weapon>,
+        WriteStorage<'s, StatusEffects>,
+        WriteStorage<'s, PickupBoosts>,
+        WriteStorage<'s, HiddenPropagate>,
+        WriteExpect<'s, MatchResource>,
+        Read<'s, Time>,
+    );
+
+    fn setup(&mut self, _world: &mut World) {
+    }
+
+    fn run(
+        &mut self,
+        (
+            entities,
+            mut players,
+            mut healths,
+            mut transforms,
+            mut movables,
+            mut shields,
+            mut weapons,
+            mut status_effects,
+            mut pickup_boosts,
+            mut hidden_propagates,
+            mut match_resource,
+            time,
+        ): Self::SystemData,
+    ) {
+        let dt = time.delta_seconds();
+
+        match_resource.update_countdown(dt);
+        match_resource.update_round_end(dt);
+
+        if match_resource.round_active() {
+            match_resource.round_time += dt;
+        }
+
+        for (entity, player, health, transform, movable, shield, weapon, status_effect, boosts) in (
+            &entities,
+            &mut players,
+            &mut healths,
+            &mut transforms,
+            &mut movables,
+            &mut shields,
+            &mut weapons,
+            &mut status_effects,
+            &mut pickup_boosts,
+        )
+            .join()
+        {
+            player.last_hit_cooldown.timer_update(&dt);
+
+            match player.get_state() {
+                PlayerState::Active if !health.is_alive() => {
+                    let killer_id = player.kill_credit();
+                    match killer_id {
+                        Some(killer_id) => log::info!("p{} destroyed by p{}", player.id + 1, killer_id + 1),
+                        None => log::info!("p{} destroyed with nobody to credit", player.id + 1),
+                    }
+
+                    match_resource.record_death(player.id, killer_id, player.killed_by_ram());
+                    player.clear_hits();
+
+                    if match_resource.lives_left(player.id) == Some(0) {
+                        log::info!("p{} eliminated", player.id + 1);
+                        player.set_state(PlayerState::Eliminated);
+                    }
+                    else if match_resource.setup.revives {
+                        log::info!("p{} down", player.id + 1);
+                        player.set_state(PlayerState::Downed);
+                    }
+                    else {
+                        player.set_state(PlayerState::InRespawn);
+                        player.respawn_cooldown.set_reset(match_resource.respawn_time(player.id));
+                        player.respawn_cooldown.timer_reset();
+                    }
+
+                    movable.dx = 0.0;
+                    movable.dy = 0.0;
+                    status_effect.clear();
+
+                    // downed ships stay visible for teammates to find
+                    if player.get_state() != PlayerState::Downed {
+                        let _ = hidden_propagates.insert(entity, HiddenPropagate::new());
+                    }
+                },
+                PlayerState::InRespawn => {
+                    player.respawn_cooldown.timer_update(&dt);
+
+                    if player.respawn_cooldown.timer_active() && match_resource.round_active() {
+                        if let Some(spawn_point) = self.spawn_points.get(player.id) {
+                            transform.set_translation_xyz(spawn_point.x, spawn_point.y, 0.0);
+                            transform.set_rotation_2d(spawn_point.rotation / 180.0 * PI);
+                        }
+
+                        health.reset();
+                        shield.power.restore();
+                        movable.power.restore();
+                        weapon.power.restore();
+                        *boosts = PickupBoosts::default();
+
+                        player.set_state(PlayerState::Active);
+                        hidden_propagates.remove(entity);
+                    }
+                },
+                _ => {},
+            }
+        }
+    }
+}
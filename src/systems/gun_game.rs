@@ -0,0 +1,147 @@
+use amethyst::{
+    core::{Time, Transform},
+    derive::SystemDesc,
+    ecs::{Join, Read, ReadExpect, ReadStorage, System, SystemData, World, WriteExpect, WriteStorage},
+};
+
+use ncollide2d::query::{self, Proximity};
+
+use crate::components::{
+    get_movable_shape_pos, Cooldown, Health, Hitbox, MatchResource, Movable, Player, Weapon, WeaponStoreResource};
+
+
+const RAM_DAMAGE: f32 = 40.0;
+const RAM_MIN_SPEED: f32 = 60.0; //slower contact is just a nudge
+const RAM_COOLDOWN: f32 = 0.5; //seconds before the same attacker can ram again
+
+
+// ClassicGunGame: every kill moves the killer to the next weapon on the match's ladder.
+// Past the last weapon the ship is holstered and has to ram, and a ram kill wins the round.
+// Getting rammed to death drops the victim back a weapon.
+#[derive(SystemDesc, Default)]
+pub struct GunGameSystem {
+    round: u32,
+    kills_read: usize, //kill feed entries already applied to the ladder
+    ram_cooldowns: Vec<Cooldown>, //by player id
+}
+
+impl<'s> System<'s> for GunGameSystem {
+    type SystemData = (
+        WriteStorage<'s, Player>,
+        WriteStorage<'s, Weapon>,
+        WriteStorage<'s, Health>,
+        ReadStorage<'s, Movable>,
+        ReadStorage<'s, Hitbox>,
+        ReadStorage<'s, Transform>,
+        WriteExpect<'s, MatchResource>,
+        ReadExpect<'s, WeaponStoreResource>,
+        Read<'s, Time>,
+    );
+
+    fn setup(&mut self, _world: &mut World) {
+    }
+
+    fn run(
+        &mut self,
+        (
+            mut players,
+            mut weapons,
+            mut healths,
+            movables,
+            hitboxes,
+            transforms,
+            mut match_resource,
+            weapon_store,
+            time,
+        ): Self::SystemData,
+    ) {
+        let dt = time.delta_seconds();
+
+        // the kill feed starts over every round
+        if self.round != match_resource.round {
+            self.round = match_resource.round;
+            self.kills_read = 0;
+        }
+
+        while self.kills_read < match_resource.kill_feed.len() && match_resource.round_active() {
+            let kill_event = match_resource.kill_feed[self.kills_read];
+            self.kills_read += 1;
+
+            match_resource.climb_ladder(&kill_event);
+        }
+
+        // Each ship carries the weapon for its tier
+        for (player, weapon) in (&players, &mut weapons).join() {
+            match match_resource.ladder_weapon(player.id) {
+                Some(weapon_name) => {
+                    if weapon.name != weapon_name || weapon.holstered {
+                        weapon.swap(weapon_name, &weapon_store.get_properties(&weapon_name));
+                        weapon.holstered = false;
+                    }
+                },
+                None => weapon.holstered = true,
+            }
+        }
+
+        if !match_resource.round_active() {
+            return;
+        }
+
+        // Ramming, for ships on the final tier
+        let ram_tier = match_resource.ram_tier();
+
+        let teams = match_resource.setup.teams;
+
+        let rammers: Vec<_> = (&players, &movables, &hitboxes, &transforms)
+            .join()
+            .filter(|(player, movable, _, _)| {
+                let tier = match_resource.scores.get(player.id).map_or(0, |score| score.tier);
+                let speed = (movable.dx.powi(2) + movable.dy.powi(2)).sqrt();
+
+                player.is_active() && tier >= ram_tier && speed >= RAM_MIN_SPEED
+            })
+            .map(|(player, _, hitbox, transform)| {
+                let (pos, shape) = get_movable_shape_pos(transform, hitbox);
+                (player.id, player.team, pos, shape)
+            })
+            .collect();
+
+        for cooldown in self.ram_cooldowns.iter_mut() {
+            cooldown.timer_update(&dt);
+        }
+
+        for (player, health, hitbox, transform) in (&mut players, &mut healths, &hitboxes, &transforms).join() {
+            if !player.is_active() {
+                continue;
+            }
+
+            let (pos, shape) = get_movable_shape_pos(transform, hitbox);
+
+            for (rammer_id, rammer_team, rammer_pos, rammer_shape) in rammers.iter() {
+                if *rammer_id == player.id || (teams && *rammer_team == player.team) {
+                    continue;
+                }
+
+                if self.ram_cooldowns.len() <= *rammer_id {
+                    self.ram_cooldowns.resize_with(*rammer_id + 1, || Cooldown::new(0.0, RAM_COOLDOWN));
+                }
+
+                if !self.ram_cooldowns[*rammer_id].timer_active() {
+                    continue;
+                }
+
+                if query::proximity(rammer_pos, rammer_shape, &pos, &shape, 0.0) == Proximity::Intersecting {
+                    log::info!("p{} rammed p{}", rammer_id + 1, player.id + 1);
+
+                    health.damage(match_resource.scale_damage(RAM_DAMAGE, Some(*rammer_id), Some(player.id)));
+                    player.rammed_by(*rammer_id);
+                    self.ram_cooldowns[*rammer_id].timer_reset();
+                }
+            }
+        }
+
+        let scores: Vec<i32> = match_resource.team_scores(|score| score.tier as i32);
+
+        match_resource.check_time_limit(&scores);
+    }
+}
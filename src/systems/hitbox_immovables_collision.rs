@@ -1,34 +1,46 @@
 use amethyst::{
-    core::{Time, Transform},
+    core::Transform,
     derive::SystemDesc,
     ecs::{
-        Entities, Join, Read, System, SystemData, World,
+        Join, System, SystemData, World,
         WriteStorage, ReadStorage,
     },
 };
 
 extern crate nalgebra as na;
-use na::{Isometry2, Vector2};
-use ncollide2d::query::{self, Proximity};
-use ncollide2d::shape::{Ball, Cuboid};
+use na::{Isometry2};
+use ncollide2d::query;
+use ncollide2d::shape::ShapeHandle;
 
 use crate::components::{
-    ArenaElement, Movable, get_movable_shape_pos, 
-    CollisionType, calc_bounce_angle, Mass, Hitbox, HitboxShape};
+    ArenaElement, ArenaElementKinds, Movable, MovingElement, get_fixed_shape_pos, get_movable_shape_pos,
+    get_moving_shape_pos, calc_moving_wall_bounce, CollisionType, Mass, Hitbox, WeaponFire};
 
+
+// A wall's collider and how it moves, velocity in units per second and spin in radians per second
+struct WallCollider {
+    pos: Isometry2<f32>,
+    shape: ShapeHandle<f32>,
+    dx: f32,
+    dy: f32,
+    spin: f32,
+}
+
+// Keeps ships and other massive movables out of arena walls, bouncing them off.
+// Moving walls push whatever they sweep into, weapon fire still passes through walls.
 #[derive(SystemDesc, Default)]
 pub struct HitboxImmovableCollisionDetection {
 }
 
 impl<'s> System<'s> for HitboxImmovableCollisionDetection {
     type SystemData = (
-        Entities<'s>,
         ReadStorage<'s, Hitbox>,
         WriteStorage<'s, Movable>,
         ReadStorage<'s, ArenaElement>,
+        ReadStorage<'s, MovingElement>,
         ReadStorage<'s, Mass>,
+        ReadStorage<'s, WeaponFire>,
         WriteStorage<'s, Transform>,
-        Read<'s, Time>,
     );
 
     fn setup(&mut self, _world: &mut World) {
@@ -37,36 +49,75 @@ impl<'s> System<'s> for HitboxImmovableCollisionDetection {
     fn run(
         &mut self,
         (
-            entities,
             hitboxes,
             mut movables,
             arena_elements,
+            moving_elements,
             masses,
+            weapon_fires,
             mut transforms,
-            time,
         ): Self::SystemData,
     ) {
-        let dt = time.delta_seconds();
+        let walls: Vec<WallCollider> = (&hitboxes, &arena_elements, &transforms, (&moving_elements).maybe())
+            .join()
+            .filter(|(_, arena_element, _, _)| arena_element.kind == ArenaElementKinds::Wall)
+            .map(|(hitbox, _, transform, moving_element)| {
+                match moving_element {
+                    Some(moving_element) => {
+                        let (pos, shape) = get_moving_shape_pos(
+                            transform, hitbox, moving_element.get_origin_rotation());
+                        WallCollider {
+                            pos,
+                            shape,
+                            dx: moving_element.dx,
+                            dy: moving_element.dy,
+                            spin: moving_element.spin,
+                        }
+                    },
+                    None => {
+                        let (pos, shape) = get_fixed_shape_pos(transform, hitbox);
+                        WallCollider {pos, shape, dx: 0.0, dy: 0.0, spin: 0.0}
+                    },
+                }
+            })
+            .collect();
 
         // For movable, mass, hitboxes
-        for (entity, movable, mass, hitbox, transform) in (
-            &entities,
+        for (movable, _mass, hitbox, transform, _) in (
             &mut movables,
             &masses,
             &hitboxes,
             &mut transforms,
+            !&weapon_fires,
         )
             .join()
         {
-            // For non-movable arena hitboxes
-            for (arena_hitbox, arena_element) in (
-                &hitboxes,
-                &arena_elements,
-            )
-                .join()
-            {
-                
+            if let CollisionType::_Through = movable.collision_type {
+                continue;
+            }
+
+            for wall in walls.iter() {
+                let (movable_pos, movable_shape) = get_movable_shape_pos(transform, hitbox);
+
+                let contact = query::contact(&wall.pos, &*wall.shape, &movable_pos, &movable_shape, 0.0);
+
+                if let Some(contact) = contact {
+                    // push out of the wall, the normal points from the wall toward the movable
+                    let normal = contact.normal;
+                    transform.prepend_translation_x(normal.x * contact.depth);
+                    transform.prepend_translation_y(normal.y * contact.depth);
+
+                    // a turning wall moves faster further from its center
+                    let offset = contact.world1.coords - wall.pos.translation.vector;
+                    let wall_dx = wall.dx - wall.spin * offset.y;
+                    let wall_dy = wall.dy + wall.spin * offset.x;
+
+                    let (dx, dy) = calc_moving_wall_bounce(
+                        normal.x, normal.y, wall_dx, wall_dy, movable.dx, movable.dy);
+                    movable.dx = dx;
+                    movable.dy = dy;
+                }
             }
         }
     }
-}
\ No newline at end of file
+}
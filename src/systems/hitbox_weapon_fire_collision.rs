@@ -0,0 +1,187 @@
+use amethyst::{
+    core::Transform,
+    derive::SystemDesc,
+    ecs::{
+        Entities, Join, ReadExpect, System, SystemData, World,
+        WriteStorage, ReadStorage,
+    },
+};
+
+use log::debug;
+use ncollide2d::query::{self, Proximity};
+
+use crate::components::{
+    calc_hit_subsystem, calc_shield_coverage_deg, get_movable_shape_pos, Drone, Health, Hitbox, MatchResource, Movable, PickupBoosts, Player,
+    PowerSubsystems, Shield, StatusEffects, Weapon, WeaponFire};
+
+#[derive(SystemDesc, Default)]
+pub struct HitboxWeaponFireCollisionDetection {
+}
+
+impl<'s> System<'s> for HitboxWeaponFireCollisionDetection {
+    type SystemData = (
+        Entities<'s>,
+        ReadStorage<'s, WeaponFire>,
+        WriteStorage<'s, Player>,
+        ReadStorage<'s, Hitbox>,
+        ReadStorage<'s, Transform>,
+        ReadStorage<'s, PickupBoosts>,
+        WriteStorage<'s, Health>,
+        WriteStorage<'s, StatusEffects>,
+        WriteStorage<'s, Shield>,
+        WriteStorage<'s, Movable>,
+        WriteStorage<'s, Weapon>,
+        WriteStorage<'s, Drone>,
+        ReadExpect<'s, MatchResource>,
+    );
+
+    fn setup(&mut self, _world: &mut World) {
+    }
+
+    fn run(
+        &mut self,
+        (
+            entities,
+            weapon_fires,
+            mut players,
+            hitboxes,
+            transforms,
+            pickup_boosts,
+            mut healths,
+            mut status_effects,
+            mut shields,
+            mut movables,
+            mut weapons,
+            mut drones,
+            match_resource,
+        ): Self::SystemData,
+    ) {
+        for (weapon_fire_entity, weapon_fire, weapon_fire_hitbox, weapon_fire_transform) in (
+            &entities,
+            &weapon_fires,
+            &hitboxes,
+            &transforms,
+        )
+            .join()
+        {
+            let (fire_pos, fire_shape) = get_movable_shape_pos(weapon_fire_transform, weapon_fire_hitbox);
+            let mut fire_hit = false;
+
+            // no friendly fire, shots pass through teammates
+            let owner_team = match (match_resource.setup.teams, weapon_fire.owner_id) {
+                (true, Some(owner_id)) => Some(match_resource.get_team(owner_id)),
+                _ => None,
+            };
+
+            for (player, player_hitbox, player_transform, boosts, health, status_effect, shield, movable, weapon) in (
+                &mut players,
+                &hitboxes,
+                &transforms,
+                &pickup_boosts,
+                &mut healths,
+                &mut status_effects,
+                &mut shields,
+                &mut movables,
+                &mut weapons,
+            )
+                .join()
+            {
+                if Some(player.id) == weapon_fire.owner_id || !player.is_active() || owner_team == Some(player.team) {
+                    continue;
+                }
+
+                let (player_pos, player_shape) = get_movable_shape_pos(player_transform, player_hitbox);
+
+                let proximity = query::proximity(
+                    &fire_pos,
+                    &fire_shape,
+                    &player_pos,
+                    &player_shape,
+                    0.0,
+                );
+
+                if proximity == Proximity::Intersecting {
+                    // an overcharged shield absorbs everything
+                    if !boosts.shield_overcharge_active() {
+                        let damage = match_resource.scale_damage(weapon_fire.damage, weapon_fire.owner_id, Some(player.id));
+
+                        health.damage(damage);
+
+                        // drone shots give no kill credit, a drone kill is an environment death
+                        if let Some(owner_id) = weapon_fire.owner_id {
+                            player.hit_by(owner_id);
+                        }
+
+                        if let Some(effect) = weapon_fire.status_effect {
+                            status_effect.apply(effect, weapon_fire.owner_id);
+                        }
+
+                        let (_, _, player_angle) = player_transform.rotation().euler_angles();
+                        let hit_subsystem = calc_hit_subsystem(
+                            player_transform.translation().x,
+                            player_transform.translation().y,
+                            player_angle,
+                            shield.angle,
+                            calc_shield_coverage_deg(shield.power.get_power_pct()),
+                            weapon_fire_transform.translation().x,
+                            weapon_fire_transform.translation().y,
+                        );
+
+                        let hit_power = match hit_subsystem {
+                            PowerSubsystems::Shield => &mut shield.power,
+                            PowerSubsystems::Thrust => &mut movable.power,
+                            PowerSubsystems::Weapon => &mut weapon.power,
+                        };
+
+                        if hit_power.hit(damage) {
+                            log::warn!("p{} {:?} damaged by hit, base level now {}",
+                                player.id + 1, hit_subsystem, hit_power.get_level_base());
+                        }
+                    }
+
+                    match weapon_fire.owner_id {
+                        Some(owner_id) => debug!("p{} hit p{}, health:{:?}", owner_id + 1, player.id + 1, health.value),
+                        None => debug!("drone hit p{}, health:{:?}", player.id + 1, health.value),
+                    }
+
+                    let _ = entities.delete(weapon_fire_entity);
+                    fire_hit = true;
+                    break;
+                }
+            }
+
+            // drones only take damage from players
+            let owner_id = match weapon_fire.owner_id {
+                Some(owner_id) if !fire_hit => owner_id,
+                _ => continue,
+            };
+
+            for (drone, drone_hitbox, drone_transform, health) in (
+                &mut drones,
+                &hitboxes,
+                &transforms,
+                &mut healths,
+            )
+                .join()
+            {
+                let (drone_pos, drone_shape) = get_movable_shape_pos(drone_transform, drone_hitbox);
+
+                let proximity = query::proximity(
+                    &fire_pos,
+                    &fire_shape,
+                    &drone_pos,
+                    &drone_shape,
+                    0.0,
+                );
+
+                if proximity == Proximity::Intersecting && health.is_alive() {
+                    health.damage(match_resource.scale_damage(weapon_fire.damage, Some(owner_id), None));
+                    drone.hit_by(owner_id);
+
+                    let _ = entities.delete(weapon_fire_entity);
+                    break;
+                }
+            }
+        }
+    }
+}
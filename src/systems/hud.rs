@@ -0,0 +1,259 @@
+use amethyst::{
+    derive::SystemDesc,
+    ecs::{Join, ReadExpect, ReadStorage, System, SystemData, World, WriteStorage},
+    ui::UiText,
+};
+
+use std::collections::HashMap;
+
+use crate::components::{
+    GameModes, Health, KillEvent, KillFeedHud, MatchHud, MatchResource, Movable, Player, PlayerHud, PlayerState,
+    Powerable, RaceProgress, RoundState, Shield, SuddenDeathRules, Weapon};
+
+#[derive(SystemDesc, Default)]
+pub struct HudSystem {
+}
+
+impl<'s> System<'s> for HudSystem {
+    type SystemData = (
+        ReadStorage<'s, Player>,
+        ReadStorage<'s, Health>,
+        ReadStorage<'s, Shield>,
+        ReadStorage<'s, Movable>,
+        ReadStorage<'s, Weapon>,
+        ReadStorage<'s, RaceProgress>,
+        ReadStorage<'s, PlayerHud>,
+        ReadStorage<'s, MatchHud>,
+        ReadStorage<'s, KillFeedHud>,
+        WriteStorage<'s, UiText>,
+        ReadExpect<'s, MatchResource>,
+    );
+
+    fn setup(&mut self, _world: &mut World) {
+    }
+
+    fn run(
+        &mut self,
+        (
+            players,
+            healths,
+            shields,
+            movables,
+            weapons,
+            race_progresses,
+            player_huds,
+            match_huds,
+            kill_feed_huds,
+            mut ui_texts,
+            match_resource,
+        ): Self::SystemData,
+    ) {
+        let mut id_match_hud_text: HashMap<usize, String> = HashMap::new();
+
+        let finish_times: Vec<f32> = (&race_progresses)
+            .join()
+            .filter_map(|race_progress| race_progress.finish_time)
+            .collect();
+
+        for (player, health, shield, movable, weapon, race_progress) in (
+            &players,
+            &healths,
+            &shields,
+            &movables,
+            &weapons,
+            (&race_progresses).maybe(),
+        )
+            .join()
+        {
+            let mut hud_text = format!(
+                "P{} HP:{:.0} S:{} T:{} W:{}",
+                player.id + 1,
+                health.value,
+                format_power(&shield.power),
+                format_power(&movable.power),
+                format_power(&weapon.power),
+            );
+
+            if match_resource.setup.teams {
+                hud_text.insert_str(0, &format!("T{} ", player.team + 1));
+            }
+
+            if let Some(race_progress) = race_progress {
+                match race_progress.finish_time {
+                    Some(finish_time) => {
+                        let place = finish_times.iter().filter(|time| **time < finish_time).count() + 1;
+                        hud_text.push_str(&format!(" FIN:{} {:.1}s", place, finish_time));
+                    },
+                    None => {
+                        let lap = (race_progress.laps + 1).min(match_resource.setup.laps);
+                        hud_text.push_str(&format!(" Lap:{}/{}", lap, match_resource.setup.laps));
+                    },
+                }
+
+                if let Some(lap_time) = race_progress.lap_times.last() {
+                    hud_text.push_str(&format!(" Last:{:.1}s", lap_time));
+                }
+
+                if race_progress.wrong_way {
+                    hud_text.push_str(" WRONG WAY");
+                }
+            }
+            else if let Some(score) = match_resource.scores.get(player.id) {
+                hud_text.push_str(&format!(" K:{} D:{}", score.kill_score(), score.deaths));
+
+                if match_resource.game_mode == GameModes::ClassicGunGame {
+                    match match_resource.ladder_weapon(player.id) {
+                        Some(weapon_name) => hud_text.push_str(&format!(
+                            " Gun:{}/{} {:?}", score.tier + 1, match_resource.ram_tier() + 1, weapon_name)),
+                        None => hud_text.push_str(" Gun:RAM"),
+                    }
+                }
+            }
+
+            if match_resource.setup.score_target.is_some() {
+                if let Some(score) = match_resource.scores.get(player.id) {
+                    hud_text.push_str(&format!(" Pts:{:.0}", score.score.floor()));
+                }
+            }
+
+            if let Some(lives) = match_resource.lives_left(player.id) {
+                hud_text.push_str(&format!(" L:{}", lives));
+            }
+
+            match player.get_state() {
+                PlayerState::InRespawn => hud_text.push_str(" RESPAWNING"),
+                PlayerState::Eliminated => hud_text.push_str(" OUT"),
+                PlayerState::Downed => hud_text.push_str(" DOWN"),
+                PlayerState::Active => {},
+            }
+
+            if match_resource.counting_down() {
+                hud_text.push_str(&format!(" START:{:.0}", match_resource.countdown.ceil()));
+            }
+
+            id_match_hud_text.insert(player.id, hud_text);
+        }
+
+        for (player_hud, ui_text) in (&player_huds, &mut ui_texts).join() {
+            if let Some(hud_text) = id_match_hud_text.get(&player_hud.id) {
+                if ui_text.text != *hud_text {
+                    ui_text.text = hud_text.clone();
+                }
+            }
+        }
+
+        let match_hud_text = format_match_hud(&match_resource);
+
+        for (_, ui_text) in (&match_huds, &mut ui_texts).join() {
+            if ui_text.text != match_hud_text {
+                ui_text.text = match_hud_text.clone();
+            }
+        }
+
+        let kill_feed_text: Vec<String> = match_resource.recent_kills().iter().map(format_kill).collect();
+        let kill_feed_text = kill_feed_text.join("\n");
+
+        for (_, ui_text) in (&kill_feed_huds, &mut ui_texts).join() {
+            if ui_text.text != kill_feed_text {
+                ui_text.text = kill_feed_text.clone();
+            }
+        }
+    }
+}
+
+
+fn format_kill(kill_event: &KillEvent) -> String {
+    match (kill_event.killer, kill_event.rammed) {
+        (Some(killer), true) => format!("P{} rammed P{}", killer + 1, kill_event.victim + 1),
+        (Some(killer), false) => format!("P{} destroyed P{}", killer + 1, kill_event.victim + 1),
+        (None, _) => format!("P{} was destroyed", kill_event.victim + 1),
+    }
+}
+
+
+// Round and clock while playing, the result during the round end freeze,
+// then the scoreboard through the intermission
+fn format_match_hud(match_resource: &MatchResource) -> String {
+    let name = |id: usize| if match_resource.setup.teams {
+        format!("Team {}", id + 1)
+    } else {
+        format!("P{}", id + 1)
+    };
+
+    let result = match match_resource.state {
+        RoundState::RoundOver(Some(winner)) => format!("{} wins the round", name(winner)),
+        RoundState::MatchOver(Some(winner)) => format!("{} wins the match", name(winner)),
+        RoundState::RoundOver(None) => "Round drawn".to_string(),
+        RoundState::MatchOver(None) => "Match drawn".to_string(),
+        RoundState::Countdown => {
+            return format!("Round {}/{}  {:.0}", match_resource.round + 1, match_resource.setup.rounds,
+                match_resource.countdown.ceil());
+        },
+        RoundState::Playing => {
+            let mut hud_text = format!("Round {}/{}", match_resource.round + 1, match_resource.setup.rounds);
+
+            if let Some(time_left) = match_resource.time_left() {
+                let seconds = time_left.ceil() as u32;
+                hud_text.push_str(&format!("  {}:{:02}", seconds / 60, seconds % 60));
+            }
+
+            match match_resource.sudden_death() {
+                SuddenDeathRules::OneHitKills => hud_text.push_str("  SUDDEN DEATH: ONE HIT KILLS"),
+                SuddenDeathRules::ShrinkingArena(_) => hud_text.push_str("  SUDDEN DEATH: ARENA SHRINKING"),
+                SuddenDeathRules::Off if match_resource.in_overtime() => hud_text.push_str("  OVERTIME"),
+                SuddenDeathRules::Off => {},
+            }
+
+            return hud_text;
+        },
+    };
+
+    if !match_resource.in_intermission() {
+        return result;
+    }
+
+    let entries = if match_resource.setup.teams {
+        match_resource.team_scores(|_| 0).len()
+    } else {
+        match_resource.round_wins.len()
+    };
+
+    let mut scoreboard = result;
+    for (id, wins) in match_resource.round_wins.iter().enumerate().take(entries) {
+        scoreboard.push_str(&format!("\n{}  Wins:{}", name(id), wins));
+
+        // round scores are by player, even in team modes
+        if !match_resource.setup.teams {
+            if let Some(score) = match_resource.scores.get(id) {
+                scoreboard.push_str(&format!("  K:{} D:{}", score.kill_score(), score.deaths));
+            }
+        }
+    }
+
+    // results are read with the handicaps in mind
+    let handicaps: Vec<String> = match_resource.handicaps
+        .iter()
+        .enumerate()
+        .filter(|(_, handicap)| !handicap.is_even())
+        .map(|(player_id, handicap)| format!("P{} {}", player_id + 1, handicap.describe()))
+        .collect();
+
+    if !handicaps.is_empty() {
+        scoreboard.push_str(&format!("\nHandicaps: {}", handicaps.join(", ")));
+    }
+    scoreboard
+}
+
+
+// level/base, with the levels lost to damage appended
+fn format_power(power: &Powerable) -> String {
+    if power.is_damaged() {
+        format!("{}/{}(-{})",
+            power.get_level(),
+            power.get_level_base(),
+            power.get_level_base_nominal() - power.get_level_base())
+    }
+    else {
+        format!("{}/{}", power.get_level(), power.get_level_base())
+    }
+}
@@ -0,0 +1,163 @@
+use amethyst::{
+    core::{Time, Transform},
+    derive::SystemDesc,
+    ecs::{Join, Read, ReadStorage, System, SystemData, World, WriteExpect, WriteStorage},
+    renderer::{palette::Srgba, resources::Tint},
+};
+
+use ncollide2d::query::{self, Proximity};
+
+use crate::components::{
+    get_fixed_shape_pos, get_leaders, get_movable_shape_pos, Cooldown, HillZone, Hitbox, MatchResource, Player,
+    ZoneStates};
+use crate::entities::{get_team_tint, ZONE_NEUTRAL_TINT};
+
+
+const ZONE_INACTIVE_ALPHA: f32 = 0.15;
+const ZONE_CONTESTED_TINT: [f32; 4] = [1.0, 0.5, 1.0, 0.8];
+
+
+// KingOfTheHill: score a point per second holding the active zone alone.
+// The first to the score target wins, otherwise the best score when the time limit runs out.
+// Zones are held by team, so teammates share a zone and split its points.
+#[derive(SystemDesc, Default)]
+pub struct KingOfTheHillSystem {
+    active_zone: usize,
+    zone_rotation: Option<Cooldown>,
+}
+
+impl<'s> System<'s> for KingOfTheHillSystem {
+    type SystemData = (
+        ReadStorage<'s, Player>,
+        ReadStorage<'s, Hitbox>,
+        ReadStorage<'s, Transform>,
+        WriteStorage<'s, HillZone>,
+        WriteStorage<'s, Tint>,
+        WriteExpect<'s, MatchResource>,
+        Read<'s, Time>,
+    );
+
+    fn setup(&mut self, world: &mut World) {
+        let zone_rotation_time = world.read_resource::<MatchResource>().setup.zone_rotation_time;
+
+        self.active_zone = 0;
+        self.zone_rotation = zone_rotation_time
+            .map(|rotation_time| Cooldown::new(rotation_time, rotation_time));
+    }
+
+    fn run(
+        &mut self,
+        (
+            players,
+            hitboxes,
+            transforms,
+            mut hill_zones,
+            mut tints,
+            mut match_resource,
+            time,
+        ): Self::SystemData,
+    ) {
+        if !match_resource.round_active() {
+            return;
+        }
+
+        let dt = time.delta_seconds();
+        let zone_count = (&hill_zones).join().count();
+        if zone_count == 0 {
+            return;
+        }
+
+        if let Some(zone_rotation) = self.zone_rotation.as_mut() {
+            zone_rotation.timer_update(&dt);
+            if zone_rotation.timer_active() {
+                zone_rotation.timer_reset();
+                self.active_zone = (self.active_zone + 1) % zone_count;
+                log::info!("hill moved to zone {}", self.active_zone);
+            }
+        }
+
+        let capture_time = match_resource.setup.zone_capture_time;
+
+        for (hill_zone, zone_hitbox, zone_transform, tint) in (
+            &mut hill_zones,
+            &hitboxes,
+            &transforms,
+            &mut tints,
+        )
+            .join()
+        {
+            let active = hill_zone.index == self.active_zone;
+            if hill_zone.active != active {
+                hill_zone.active = active;
+                hill_zone.reset();
+            }
+
+            if !active {
+                let [r, g, b, _] = ZONE_NEUTRAL_TINT;
+                *tint = Tint(Srgba::new(r, g, b, ZONE_INACTIVE_ALPHA));
+                continue;
+            }
+
+            let (zone_pos, zone_shape) = get_fixed_shape_pos(zone_transform, zone_hitbox);
+
+            let occupants: Vec<(usize, usize)> = (&players, &hitboxes, &transforms)
+                .join()
+                .filter(|(player, _, _)| player.is_active())
+                .filter(|(_, player_hitbox, player_transform)| {
+                    let (player_pos, player_shape) = get_movable_shape_pos(player_transform, player_hitbox);
+
+                    query::proximity(
+                        &zone_pos,
+                        &*zone_shape,
+                        &player_pos,
+                        &player_shape,
+                        0.0,
+                    ) == Proximity::Intersecting
+                })
+                .map(|(player, _, _)| (player.id, player.team))
+                .collect();
+
+            let mut occupant_teams: Vec<usize> = occupants.iter().map(|(_, team)| *team).collect();
+            occupant_teams.sort();
+            occupant_teams.dedup();
+
+            if let Some(scoring_team) = hill_zone.update(&occupant_teams, capture_time, dt) {
+                let scorers: Vec<usize> = occupants.iter()
+                    .filter(|(_, team)| *team == scoring_team)
+                    .map(|(player_id, _)| *player_id)
+                    .collect();
+
+                for scorer in scorers.iter() {
+                    if let Some(score) = match_resource.get_score_mut(*scorer) {
+                        score.score += dt / scorers.len() as f32;
+                    }
+                }
+            }
+
+            *tint = match (hill_zone.get_state(), hill_zone.get_controller()) {
+                (ZoneStates::Contested, _) => {
+                    let [r, g, b, a] = ZONE_CONTESTED_TINT;
+                    Tint(Srgba::new(r, g, b, a))
+                },
+                (_, Some(controller)) => get_team_tint(controller),
+                (_, None) => {
+                    let [r, g, b, a] = ZONE_NEUTRAL_TINT;
+                    Tint(Srgba::new(r, g, b, a))
+                },
+            };
+        }
+
+        let scores: Vec<f32> = match_resource.team_scores(|score| score.score);
+        let whole_scores: Vec<i32> = scores.iter().map(|score| score.floor() as i32).collect();
+
+        if let Some(score_target) = match_resource.setup.score_target {
+            let leaders = get_leaders(&whole_scores);
+            if leaders.len() == 1 && scores[leaders[0]] >= score_target {
+                match_resource.end_round(Some(leaders[0]));
+                return;
+            }
+        }
+
+        match_resource.check_time_limit(&whole_scores);
+    }
+}
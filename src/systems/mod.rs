@@ -5,7 +5,24 @@ mod player_systems;
 mod move_weapon_fire;
 mod hitbox_collision;
 mod hitbox_immovables_collision;
+mod arena_motion;
+mod destructible_walls;
+mod teleporters;
 mod camera_tracking;
+mod pickups;
+mod status_effects;
+mod hitbox_weapon_fire_collision;
+mod hud;
+mod game_mode;
+mod stock_rules;
+mod deathmatch_rules;
+mod king_of_the_hill;
+mod capture_the_flag;
+mod race;
+mod drones;
+mod survival;
+mod gun_game;
+mod sudden_death;
 
 pub use self::move_player::MovePlayerSystem;
 pub use self::aim_weapon_shield::AimWeaponSystem;
@@ -14,4 +31,21 @@ pub use self::player_systems::PlayerSystemsSystem;
 pub use self::move_weapon_fire::MoveWeaponFireSystem;
 pub use self::hitbox_collision::HitboxCollisionDetection;
 pub use self::hitbox_immovables_collision::HitboxImmovableCollisionDetection;
-pub use self::camera_tracking::CameraTrackingSystem;
\ No newline at end of file
+pub use self::arena_motion::ArenaMotionSystem;
+pub use self::destructible_walls::DestructibleWallSystem;
+pub use self::teleporters::TeleporterSystem;
+pub use self::camera_tracking::CameraTrackingSystem;
+pub use self::pickups::PickupSystem;
+pub use self::status_effects::StatusEffectSystem;
+pub use self::hitbox_weapon_fire_collision::HitboxWeaponFireCollisionDetection;
+pub use self::hud::HudSystem;
+pub use self::game_mode::{add_game_mode_systems, GameModeSystem};
+pub use self::stock_rules::StockRulesSystem;
+pub use self::deathmatch_rules::{KillsRulesSystem, TimedKDRulesSystem};
+pub use self::king_of_the_hill::KingOfTheHillSystem;
+pub use self::capture_the_flag::CaptureTheFlagSystem;
+pub use self::race::RaceSystem;
+pub use self::drones::DroneSystem;
+pub use self::survival::SurvivalSystem;
+pub use self::gun_game::GunGameSystem;
+pub use self::sudden_death::SuddenDeathSystem;
\ No newline at end of file
@@ -2,7 +2,7 @@ use amethyst::{
     core::{Time, Transform},
     derive::SystemDesc,
     ecs::{
-        Join, Read, System, SystemData, World,
+        Join, Read, ReadExpect, System, SystemData, World,
         WriteStorage, ReadStorage,
     },
     input::{InputHandler, StringBindings},
@@ -10,7 +10,11 @@ use amethyst::{
 
 use std::f32::consts::PI;
 
-use crate::components::{Movable, Mass, Player};
+use crate::components::{MatchResource, Movable, Mass, Player, PickupBoosts, StatusEffects};
+
+
+const DASH_IMPULSE: f32 = 150.0;
+const DASH_THRUST_COST: u8 = 3; //thrust levels spent on a dash, given back once it recovers
 
 #[derive(SystemDesc, Default)]
 pub struct MovePlayerSystem {
@@ -18,12 +22,15 @@ pub struct MovePlayerSystem {
 
 impl<'s> System<'s> for MovePlayerSystem {
     type SystemData = (
-        ReadStorage<'s, Player>,
+        WriteStorage<'s, Player>,
         WriteStorage<'s, Movable>,
         ReadStorage<'s, Mass>,
+        ReadStorage<'s, PickupBoosts>,
+        ReadStorage<'s, StatusEffects>,
         WriteStorage<'s, Transform>,
         Read<'s, Time>,
         Read<'s, InputHandler<StringBindings>>, //<MovementBindingTypes>
+        ReadExpect<'s, MatchResource>,
     );
 
     fn setup(&mut self, _world: &mut World) {
@@ -32,25 +39,49 @@ impl<'s> System<'s> for MovePlayerSystem {
     fn run(
         &mut self,
         (
-            players,
+            mut players,
             mut movables,
             masses,
+            pickup_boosts,
+            status_effects,
             mut transforms,
             time,
             input,
+            match_resource,
         ): Self::SystemData,
     ) {
+        // held on the start grid, and once the round is decided
+        if match_resource.frozen() {
+            return;
+        }
+
         let dt = time.delta_seconds();
 
-        for (player, mut movable, mass, transform) in (
-            &players,
+        for (player, mut movable, mass, boosts, status_effect, transform) in (
+            &mut players,
             &mut movables,
             &masses,
+            &pickup_boosts,
+            &status_effects,
             &mut transforms,
         )
             .join()
         {
-            let powered_max_accel_force = movable.max_accel_force * (movable.power.get_power_pct());
+            if !player.is_active() {
+                continue;
+            }
+
+            player.dash_cooldown.timer_update(&dt);
+            player.dash_thrust_drain.timer_update(&dt);
+
+            if player.dash_thrust_drain.timer_active() {
+                movable.power.restore_drain();
+            }
+
+            let powered_max_accel_force = movable.max_accel_force 
+                * (movable.power.get_power_pct())
+                * boosts.speed_boost_mult()
+                * status_effect.slow_mult();
 
             let auto_decel_force = powered_max_accel_force / 6.0; //applied when no controller input detected
             let friction_decel_force = powered_max_accel_force / 20.0; //applied always, mass cancels out
@@ -121,6 +152,37 @@ impl<'s> System<'s> for MovePlayerSystem {
                 movable.dy = 0.0;
             }
 
+            // Apply Dash burst, in the input direction if there is one, otherwise along the current velocity
+            let player_dash = match player.id {
+                0 => input.action_is_down("p1_dash"),
+                1 => input.action_is_down("p2_dash"),
+                2 => input.action_is_down("p3_dash"),
+                3 => input.action_is_down("p4_dash"),
+                _ => None,
+            }.unwrap_or(false);
+
+            if player_dash && player.dash_cooldown.timer_active() && movable.power.is_powered() {
+                let (dash_x_comp, dash_y_comp) = if player_input {
+                    let accel_angle = player_accel_y_pct.atan2(player_accel_x_pct);
+                    (accel_angle.cos(), accel_angle.sin())
+                }
+                else if sq_vel > 0.0 {
+                    (vel_x_comp, vel_y_comp)
+                }
+                else {
+                    (0.0, 0.0)
+                };
+
+                if dash_x_comp != 0.0 || dash_y_comp != 0.0 {
+                    movable.dx += DASH_IMPULSE * dash_x_comp / mass.mass;
+                    movable.dy += DASH_IMPULSE * dash_y_comp / mass.mass;
+
+                    player.dash_cooldown.timer_reset_multiplier(1.0 / movable.power.get_power_pct());
+                    player.dash_thrust_drain.timer_reset();
+                    movable.power.drain(DASH_THRUST_COST);
+                }
+            }
+
             // Apply Frictions
             movable.dx -= friction_decel_force * movable.dx.signum() * dt;
             movable.dy -= friction_decel_force * movable.dy.signum() * dt;
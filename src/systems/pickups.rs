@@ -0,0 +1,144 @@
+use amethyst::{
+    core::{Hidden, Time, Transform},
+    derive::SystemDesc,
+    ecs::{
+        Entities, Join, Read, ReadExpect, System, SystemData, World,
+        WriteStorage, ReadStorage,
+    },
+    renderer::resources::Tint,
+};
+
+use ncollide2d::query::{self, Proximity};
+
+use crate::components::{
+    get_movable_shape_pos, Health, Hitbox, Movable, Pickup, PickupBoosts, PickupKinds, Player, Shield, Weapon,
+    WeaponStoreResource, REPAIR_AMOUNT, SHIELD_OVERCHARGE_TIME, SPEED_BOOST_TIME};
+use crate::entities::get_pickup_tint;
+
+#[derive(SystemDesc, Default)]
+pub struct PickupSystem {
+}
+
+impl<'s> System<'s> for PickupSystem {
+    type SystemData = (
+        Entities<'s>,
+        ReadStorage<'s, Player>,
+        WriteStorage<'s, Pickup>,
+        WriteStorage<'s, PickupBoosts>,
+        WriteStorage<'s, Health>,
+        WriteStorage<'s, Weapon>,
+        WriteStorage<'s, Shield>,
+        WriteStorage<'s, Movable>,
+        ReadStorage<'s, Hitbox>,
+        ReadStorage<'s, Transform>,
+        WriteStorage<'s, Hidden>,
+        WriteStorage<'s, Tint>,
+        ReadExpect<'s, WeaponStoreResource>,
+        Read<'s, Time>,
+    );
+
+    fn setup(&mut self, _world: &mut World) {
+    }
+
+    fn run(
+        &mut self,
+        (
+            entities,
+            players,
+            mut pickups,
+            mut pickup_boosts,
+            mut healths,
+            mut weapons,
+            mut shields,
+            mut movables,
+            hitboxes,
+            transforms,
+            mut hiddens,
+            mut tints,
+            weapon_store,
+            time,
+        ): Self::SystemData,
+    ) {
+        let dt = time.delta_seconds();
+
+        for boosts in (&mut pickup_boosts).join() {
+            boosts.timer_update(&dt);
+        }
+
+        for (pickup_entity, pickup, pickup_hitbox, pickup_transform) in (
+            &entities,
+            &mut pickups,
+            &hitboxes,
+            &transforms,
+        )
+            .join()
+        {
+            if !pickup.available {
+                pickup.respawn.timer_update(&dt);
+
+                if pickup.respawn.timer_active() {
+                    pickup.available = true;
+                    hiddens.remove(pickup_entity);
+                    let _ = tints.insert(pickup_entity, get_pickup_tint(&pickup.kind()));
+                }
+                continue;
+            }
+
+            let (pickup_pos, pickup_shape) = get_movable_shape_pos(pickup_transform, pickup_hitbox);
+
+            for (player, player_hitbox, player_transform, health, weapon, shield, movable, boosts) in (
+                &players,
+                &hitboxes,
+                &transforms,
+                &mut healths,
+                &mut weapons,
+                &mut shields,
+                &mut movables,
+                &mut pickup_boosts,
+            )
+                .join()
+            {
+                if !player.is_active() {
+                    continue;
+                }
+
+                let (player_pos, player_shape) = get_movable_shape_pos(player_transform, player_hitbox);
+
+                let proximity = query::proximity(
+                    &player_pos,
+                    &player_shape,
+                    &pickup_pos,
+                    &pickup_shape,
+                    0.0,
+                );
+
+                if proximity == Proximity::Intersecting {
+                    let kind = pickup.collect();
+
+                    match kind {
+                        PickupKinds::Repair => {
+                            health.repair(REPAIR_AMOUNT);
+                            shield.power.repair();
+                            movable.power.repair();
+                            weapon.power.repair();
+                        },
+                        PickupKinds::ShieldOvercharge => {
+                            boosts.shield_overcharge = SHIELD_OVERCHARGE_TIME;
+                        },
+                        PickupKinds::WeaponSwap(weapon_name) => {
+                            weapon.swap(weapon_name, &weapon_store.get_properties(&weapon_name));
+                        },
+                        PickupKinds::SpeedBoost => {
+                            boosts.speed_boost = SPEED_BOOST_TIME;
+                        },
+                    }
+
+                    log::info!("p{} picked up {:?}", player.id + 1, kind);
+
+                    let _ = hiddens.insert(pickup_entity, Hidden);
+                    break;
+                }
+            }
+        }
+    }
+}
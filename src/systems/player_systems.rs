@@ -1,10 +1,11 @@
 use amethyst::{core::{Time}, derive::SystemDesc, ecs::{
         Join, Read, System, SystemData, World,
-        WriteStorage,
+        WriteStorage, ReadStorage,
     }, input::{InputHandler, StringBindings}
 };
 
-use crate::components::{Movable, Player, Shield, Weapon};
+use crate::components::{
+    get_subsystem_powers, Movable, Player, PowerBus, PowerPresets, PowerSubsystems, Shield, Weapon};
 
 #[derive(SystemDesc, Default)]
 pub struct PlayerSystemsSystem {
@@ -13,6 +14,8 @@ pub struct PlayerSystemsSystem {
 impl<'s> System<'s> for PlayerSystemsSystem {
     type SystemData = (
         WriteStorage<'s, Player>,
+        ReadStorage<'s, PowerBus>,
+        WriteStorage<'s, PowerPresets>,
         WriteStorage<'s, Shield>,
         WriteStorage<'s, Movable>,
         WriteStorage<'s, Weapon>,
@@ -27,6 +30,8 @@ impl<'s> System<'s> for PlayerSystemsSystem {
         &mut self,
         (
             mut players,
+            power_buses,
+            mut player_power_presets,
             mut shields,
             mut movables,
             mut weapons,
@@ -36,55 +41,97 @@ impl<'s> System<'s> for PlayerSystemsSystem {
     ) {
         let dt = time.delta_seconds();
 
-        for (player, shield, movable, weapon) in (
+        for (player, power_bus, power_presets, shield, movable, weapon) in (
             &mut players,
+            &power_buses,
+            &mut player_power_presets,
             &mut shields,
             &mut movables,
             &mut weapons,
         )
             .join()
         {
+            let (
+                adjust_reset_system,
+                adjust_shield_system,
+                adjust_thrust_system,
+                adjust_weapon_system,
+            ) = get_player_systems_controller_input(&player.id, &input);
+
+            let mut powers = get_subsystem_powers(power_bus, shield, movable, weapon);
+
+            // Long-press on an adjust button jumps to a preset, a short press adjusts power on release
+            let (tapped, selected_preset) = power_presets.update_holds(&dt, [
+                adjust_reset_system,
+                adjust_shield_system,
+                adjust_thrust_system,
+                adjust_weapon_system,
+            ]);
+
+            if let Some(preset) = selected_preset {
+                power_presets.target = Some(power_bus.preset_levels(&powers, &preset.weights));
+
+                log::info!("p{} power preset: {}", player.id + 1, preset.name);
+            }
+
             player.system_adjust_cooldown.timer_update(&dt);
             if player.system_adjust_cooldown.timer_active() {
-
-                let (
-                    adjust_reset_system,
-                    adjust_shield_system,
-                    adjust_thrust_system,
-                    adjust_weapon_system,
-                ) = get_player_systems_controller_input(&player.id, &input);
-
                 let mut adjust_attempted: bool = false;
 
-                if adjust_reset_system {
-                    shield.power.reset();
-                    movable.power.reset();
-                    weapon.power.reset();
+                if tapped[0] {
+                    power_bus.reset(&mut powers);
                     adjust_attempted = true;
                 }
 
-                if adjust_shield_system {
-                    shield.power.up(movable.power.down() + weapon.power.down());
+                if tapped[1] {
+                    power_bus.shift_to(&mut powers, &PowerSubsystems::Shield);
                     adjust_attempted = true;
                 }
 
-                if adjust_thrust_system {
-                    movable.power.up(shield.power.down() + weapon.power.down());
+                if tapped[2] {
+                    power_bus.shift_to(&mut powers, &PowerSubsystems::Thrust);
                     adjust_attempted = true;
                 }
 
-                if adjust_weapon_system {
-                    weapon.power.up(shield.power.down() + movable.power.down());
+                if tapped[3] {
+                    power_bus.shift_to(&mut powers, &PowerSubsystems::Weapon);
                     adjust_attempted = true;
                 }
 
                 
                 if adjust_attempted {
                     player.system_adjust_cooldown.timer_reset();
+                    power_presets.target = None; //manual adjustments cancel a preset transition
 
-                    log::info!("p1 s:{:?}, t:{:?}, w:{:?}", shield.power, movable.power, weapon.power);
+                    log::info!("p{} {:?}: {:?}", player.id + 1, power_bus.subsystems, powers);
                 }
             }
+
+            // Move toward the selected preset one step at a time, through the same bus API
+            power_presets.transition_cooldown.timer_update(&dt);
+            if power_presets.target.is_some() && power_presets.transition_cooldown.timer_active() {
+                let target_reached = match &power_presets.target {
+                    Some(target) => power_bus.step_toward(&mut powers, target),
+                    None => true,
+                };
+
+                power_presets.transition_cooldown.timer_reset();
+
+                if target_reached {
+                    power_presets.target = None;
+                }
+            }
+
+            // Holding a subsystem above its base builds up stress, until it damages the subsystem
+            for (subsystem, power) in power_bus.subsystems.iter().zip(powers.iter_mut()) {
+                if power.update_stress(&dt) {
+                    log::warn!("p{} {:?} damaged by overcharge, base level now {}",
+                        player.id + 1, subsystem, power.get_level_base());
+                }
+            }
+
+            // power above a damaged subsystem's new base goes to the others, including after hits
+            power_bus.redistribute(&mut powers);
         }
     }
 }
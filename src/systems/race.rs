@@ -0,0 +1,159 @@
+use amethyst::{
+    core::Transform,
+    derive::SystemDesc,
+    ecs::{Join, ReadStorage, System, SystemData, World, WriteExpect, WriteStorage},
+};
+
+use ncollide2d::query::{self, Proximity};
+
+use crate::components::{
+    get_fixed_shape_pos, get_movable_shape_pos, Checkpoint, CheckpointResult, Hitbox, MatchResource, Player,
+    RaceProgress};
+
+
+// Ranks finishers above everyone still on course when the time limit runs out
+const FINISHED_SCORE: i32 = 100_000;
+
+
+// Race: pass the checkpoints in order for the mode's number of laps, the first to finish wins.
+// The round ends once everyone has finished, or on the time limit with the furthest racer leading.
+// Destroyed racers respawn at the last checkpoint they passed.
+#[derive(SystemDesc, Default)]
+pub struct RaceSystem {
+    finish_order: Vec<usize>,
+    was_active: Vec<bool>, //by player id, to catch respawns
+}
+
+impl<'s> System<'s> for RaceSystem {
+    type SystemData = (
+        ReadStorage<'s, Player>,
+        ReadStorage<'s, Hitbox>,
+        ReadStorage<'s, Checkpoint>,
+        WriteStorage<'s, Transform>,
+        WriteStorage<'s, RaceProgress>,
+        WriteExpect<'s, MatchResource>,
+    );
+
+    fn setup(&mut self, _world: &mut World) {
+        self.finish_order = vec![];
+        self.was_active = vec![];
+    }
+
+    fn run(
+        &mut self,
+        (
+            players,
+            hitboxes,
+            checkpoints,
+            mut transforms,
+            mut race_progresses,
+            mut match_resource,
+        ): Self::SystemData,
+    ) {
+        if !match_resource.round_active() {
+            return;
+        }
+
+        let checkpoint_count = (&checkpoints).join().count();
+        if checkpoint_count == 0 {
+            return;
+        }
+
+        let lap_count = match_resource.setup.laps;
+        let race_time = match_resource.round_time;
+
+        let checkpoint_shapes: Vec<_> = (&checkpoints, &hitboxes, &transforms)
+            .join()
+            .map(|(checkpoint, hitbox, transform)| {
+                let (pos, shape) = get_fixed_shape_pos(transform, hitbox);
+                (checkpoint.index, pos, shape)
+            })
+            .collect();
+
+        for (player, player_hitbox, race_progress, transform) in (
+            &players,
+            &hitboxes,
+            &mut race_progresses,
+            &mut transforms,
+        )
+            .join()
+        {
+            if self.was_active.len() <= player.id {
+                self.was_active.resize(player.id + 1, true);
+            }
+
+            let active = player.is_active();
+            let respawned = active && !self.was_active[player.id];
+            self.was_active[player.id] = active;
+
+            if respawned {
+                let last_checkpoint = (race_progress.next_checkpoint + checkpoint_count - 1) % checkpoint_count;
+                if let Some((_, checkpoint_pos, _)) = checkpoint_shapes
+                    .iter()
+                    .find(|(index, _, _)| *index == last_checkpoint)
+                {
+                    transform.set_translation_x(checkpoint_pos.translation.vector.x);
+                    transform.set_translation_y(checkpoint_pos.translation.vector.y);
+                }
+            }
+
+            if !active {
+                continue;
+            }
+
+            let (player_pos, player_shape) = get_movable_shape_pos(transform, player_hitbox);
+
+            for (index, checkpoint_pos, checkpoint_shape) in checkpoint_shapes.iter() {
+                let touching = query::proximity(
+                    checkpoint_pos,
+                    &**checkpoint_shape,
+                    &player_pos,
+                    &player_shape,
+                    0.0,
+                ) == Proximity::Intersecting;
+
+                if !touching {
+                    continue;
+                }
+
+                match race_progress.touch_checkpoint(*index, checkpoint_count, lap_count, race_time) {
+                    Some(CheckpointResult::LapComplete(lap_time)) => {
+                        log::info!("p{} lap {} in {:.2}s", player.id + 1, race_progress.laps, lap_time);
+                    },
+                    Some(CheckpointResult::Finished(lap_time)) => {
+                        log::info!("p{} lap {} in {:.2}s", player.id + 1, race_progress.laps, lap_time);
+                        log::info!("p{} finished {} in {:.2}s",
+                            player.id + 1, self.finish_order.len() + 1, race_time);
+                        self.finish_order.push(player.id);
+                    },
+                    Some(CheckpointResult::WrongWay) => log::info!("p{} wrong way", player.id + 1),
+                    Some(CheckpointResult::Passed) | None => {},
+                }
+            }
+        }
+
+        // finishers by place, then everyone else by distance covered
+        let mut race_scores = vec![0; match_resource.scores.len()];
+        for (player, race_progress) in (&players, &race_progresses).join() {
+            if let Some(race_score) = race_scores.get_mut(player.id) {
+                *race_score = match self.finish_order.iter().position(|id| *id == player.id) {
+                    Some(place) => FINISHED_SCORE - place as i32,
+                    None => race_progress.distance(checkpoint_count) as i32,
+                };
+            }
+        }
+
+        let racing = (&players, &race_progresses)
+            .join()
+            .any(|(player, race_progress)| !race_progress.finished() && player.player_state_in_game());
+
+        if !self.finish_order.is_empty() && !racing {
+            let winner = self.finish_order.first().map(|player_id| match_resource.get_team(*player_id));
+            match_resource.end_round(winner);
+            return;
+        }
+
+        let race_scores = match_resource.sum_by_team(&race_scores);
+        match_resource.check_time_limit(&race_scores);
+    }
+}
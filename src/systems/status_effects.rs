@@ -0,0 +1,70 @@
+use amethyst::{
+    core::Time,
+    derive::SystemDesc,
+    ecs::{Join, Read, System, SystemData, World, WriteStorage},
+};
+
+use crate::components::{Health, Movable, Player, Shield, StatusEffects, Weapon};
+
+#[derive(SystemDesc, Default)]
+pub struct StatusEffectSystem {
+}
+
+impl<'s> System<'s> for StatusEffectSystem {
+    type SystemData = (
+        WriteStorage<'s, StatusEffects>,
+        WriteStorage<'s, Health>,
+        WriteStorage<'s, Shield>,
+        WriteStorage<'s, Movable>,
+        WriteStorage<'s, Weapon>,
+        WriteStorage<'s, Player>,
+        Read<'s, Time>,
+    );
+
+    fn setup(&mut self, _world: &mut World) {
+    }
+
+    fn run(
+        &mut self,
+        (
+            mut status_effects,
+            mut healths,
+            mut shields,
+            mut movables,
+            mut weapons,
+            mut players,
+            time,
+        ): Self::SystemData,
+    ) {
+        let dt = time.delta_seconds();
+
+        for (status_effect, health, shield, movable, weapon, player) in (
+            &mut status_effects,
+            &mut healths,
+            &mut shields,
+            &mut movables,
+            &mut weapons,
+            &mut players,
+        )
+            .join()
+        {
+            // taken before the update so a stack expiring this frame still credits its owner
+            let burn_owner = status_effect.burn_owner();
+            let burn_damage = status_effect.timer_update(&dt);
+            if burn_damage > 0.0 {
+                health.damage(burn_damage);
+
+                // keeps kill credit alive past KILL_CREDIT_TIME while the burn is still ticking
+                if let Some(owner_id) = burn_owner {
+                    player.hit_by(owner_id);
+                }
+            }
+
+            // EMP drops all subsystems to zero, the allocated levels come back when it expires
+            let emp_active = status_effect.emp_active();
+            shield.power.set_disabled(emp_active);
+            movable.power.set_disabled(emp_active);
+            weapon.power.set_disabled(emp_active);
+        }
+    }
+}
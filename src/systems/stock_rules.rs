@@ -0,0 +1,48 @@
+use amethyst::{
+    derive::SystemDesc,
+    ecs::{Join, ReadStorage, System, SystemData, World, WriteExpect},
+};
+
+use crate::components::{MatchResource, Player, PlayerState};
+
+// DeathmatchStock: the last ship, or team, with lives left wins the round
+#[derive(SystemDesc, Default)]
+pub struct StockRulesSystem {
+}
+
+impl<'s> System<'s> for StockRulesSystem {
+    type SystemData = (
+        ReadStorage<'s, Player>,
+        WriteExpect<'s, MatchResource>,
+    );
+
+    fn setup(&mut self, _world: &mut World) {
+    }
+
+    fn run(
+        &mut self,
+        (
+            players,
+            mut match_resource,
+        ): Self::SystemData,
+    ) {
+        if !match_resource.round_active() {
+            return;
+        }
+
+        // by team, so a team wins with any of its players left
+        let mut survivors: Vec<usize> = (&players)
+            .join()
+            .filter(|player| player.get_state() != PlayerState::Eliminated)
+            .map(|player| player.team)
+            .collect();
+        survivors.sort();
+        survivors.dedup();
+
+        match survivors.len() {
+            0 => match_resource.end_round(None), //last ships destroyed together
+            1 => match_resource.end_round(Some(survivors[0])),
+            _ => {},
+        }
+    }
+}
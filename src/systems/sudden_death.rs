@@ -0,0 +1,121 @@
+use amethyst::{
+    core::{math::Vector3, HiddenPropagate, Time, Transform},
+    derive::SystemDesc,
+    ecs::{
+        Entities, Join, Read, ReadExpect, ReadStorage, System, SystemData, World,
+        WriteStorage,
+    },
+};
+
+use crate::components::{Arena, Health, MatchResource, Player, SafeZoneEdge, SafeZoneSides, SuddenDeathRules};
+
+
+const SAFE_ZONE_DAMAGE: f32 = 25.0; //per second outside the safe area
+const SAFE_ZONE_EDGE_THICKNESS: f32 = 3.0;
+
+
+// Applies the mode's sudden death rule once a round goes to overtime, for every mode.
+// One-hit kills destroy a ship on any damage,
+// a shrinking arena closes in on the center and damages ships left outside.
+#[derive(SystemDesc)]
+pub struct SuddenDeathSystem {
+    arena_width: f32,
+    arena_height: f32,
+    last_health: Vec<f32>, //by player id, to spot new damage
+}
+
+impl SuddenDeathSystem {
+    pub fn new(arena_properties: &Arena) -> SuddenDeathSystem {
+        SuddenDeathSystem {
+            arena_width: arena_properties.width,
+            arena_height: arena_properties.height,
+            last_health: vec![],
+        }
+    }
+}
+
+impl<'s> System<'s> for SuddenDeathSystem {
+    type SystemData = (
+        Entities<'s>,
+        ReadStorage<'s, Player>,
+        WriteStorage<'s, Health>,
+        WriteStorage<'s, Transform>,
+        ReadStorage<'s, SafeZoneEdge>,
+        WriteStorage<'s, HiddenPropagate>,
+        ReadExpect<'s, MatchResource>,
+        Read<'s, Time>,
+    );
+
+    fn setup(&mut self, _world: &mut World) {
+    }
+
+    fn run(
+        &mut self,
+        (
+            entities,
+            players,
+            mut healths,
+            mut transforms,
+            safe_zone_edges,
+            mut hidden_propagates,
+            match_resource,
+            time,
+        ): Self::SystemData,
+    ) {
+        let dt = time.delta_seconds();
+        let sudden_death = match_resource.sudden_death();
+
+        let center_x = self.arena_width / 2.0;
+        let center_y = self.arena_height / 2.0;
+        let safe_zone_scale = match_resource.safe_zone_scale();
+        let half_width = self.arena_width / 2.0 * safe_zone_scale;
+        let half_height = self.arena_height / 2.0 * safe_zone_scale;
+
+        for (player, health, transform) in (&players, &mut healths, &transforms).join() {
+            if self.last_health.len() <= player.id {
+                self.last_health.resize(player.id + 1, health.value);
+            }
+
+            if player.is_active() {
+                match sudden_death {
+                    SuddenDeathRules::OneHitKills if health.value < self.last_health[player.id] => {
+                        health.value = 0.0;
+                    },
+                    SuddenDeathRules::ShrinkingArena(_) => {
+                        let outside = (transform.translation().x - center_x).abs() > half_width
+                            || (transform.translation().y - center_y).abs() > half_height;
+
+                        if outside {
+                            health.damage(SAFE_ZONE_DAMAGE * dt);
+                        }
+                    },
+                    _ => {},
+                }
+            }
+
+            self.last_health[player.id] = health.value;
+        }
+
+        //Rectangle sprite is 10 pixels wide and 50 pixels tall
+        for (entity, edge, transform) in (&entities, &safe_zone_edges, &mut transforms).join() {
+            if safe_zone_scale >= 1.0 {
+                if !hidden_propagates.contains(entity) {
+                    let _ = hidden_propagates.insert(entity, HiddenPropagate::new());
+                }
+                continue;
+            }
+
+            let (x, y, width, height) = match edge.side {
+                SafeZoneSides::Left => (center_x - half_width, center_y, SAFE_ZONE_EDGE_THICKNESS, half_height * 2.0),
+                SafeZoneSides::Right => (center_x + half_width, center_y, SAFE_ZONE_EDGE_THICKNESS, half_height * 2.0),
+                SafeZoneSides::Bottom => (center_x, center_y - half_height, half_width * 2.0, SAFE_ZONE_EDGE_THICKNESS),
+                SafeZoneSides::Top => (center_x, center_y + half_height, half_width * 2.0, SAFE_ZONE_EDGE_THICKNESS),
+            };
+
+            transform.set_translation_xyz(x, y, 0.3);
+            transform.set_scale(Vector3::new(width / 10.0, height / 50.0, 0.0));
+
+            hidden_propagates.remove(entity);
+        }
+    }
+}
@@ -0,0 +1,198 @@
+use amethyst::{
+    core::{Time, Transform},
+    derive::SystemDesc,
+    ecs::{
+        Entities, Join, LazyUpdate, Read, ReadExpect, ReadStorage, System, SystemData, World,
+        WriteExpect, WriteStorage,
+    },
+};
+
+use std::collections::VecDeque;
+
+use crate::components::{
+    Arena, Cooldown, Drone, DroneKinds, Health, MatchResource, Player, PlayerState, SurvivalStoreResource,
+    WeaponNames, WeaponStoreResource};
+use crate::entities::spawn_drone;
+use crate::resources::DroneSpriteResource;
+
+
+const DRONE_SPAWN_INSET: f32 = 20.0; //distance in from the arena edge
+
+
+// SurvivalWaves: co-op against escalating waves of drones entering from the arena edges.
+// Each wave starts after a break once the last is cleared. Downed players are revived
+// by a teammate staying close for long enough, and the game ends when everyone is down.
+#[derive(SystemDesc)]
+pub struct SurvivalSystem {
+    drone_spawns: Vec<(f32, f32)>,
+    wave: usize, //waves started
+    wave_in_progress: bool,
+    pending_drones: VecDeque<(DroneKinds, Option<WeaponNames>)>,
+    drone_spawn_count: usize,
+    spawn_cooldown: Cooldown,
+    wave_break: Cooldown,
+    revive_progress: Vec<f32>, //by player id, seconds
+}
+
+impl SurvivalSystem {
+    pub fn new(arena_properties: &Arena) -> SurvivalSystem {
+        let (width, height) = (arena_properties.width, arena_properties.height);
+        let (left, right) = (DRONE_SPAWN_INSET, width - DRONE_SPAWN_INSET);
+        let (bottom, top) = (DRONE_SPAWN_INSET, height - DRONE_SPAWN_INSET);
+
+        SurvivalSystem {
+            //corners and edge midpoints, cycled through
+            drone_spawns: vec![
+                (left, top), (width / 2.0, bottom), (right, top), (left, height / 2.0),
+                (right, bottom), (width / 2.0, top), (left, bottom), (right, height / 2.0),
+            ],
+            wave: 0,
+            wave_in_progress: false,
+            pending_drones: VecDeque::new(),
+            drone_spawn_count: 0,
+            spawn_cooldown: Cooldown::new(0.0, 0.0),
+            wave_break: Cooldown::new(0.0, 0.0),
+            revive_progress: vec![],
+        }
+    }
+}
+
+impl<'s> System<'s> for SurvivalSystem {
+    type SystemData = (
+        Entities<'s>,
+        ReadStorage<'s, Drone>,
+        WriteStorage<'s, Player>,
+        WriteStorage<'s, Health>,
+        ReadStorage<'s, Transform>,
+        WriteExpect<'s, MatchResource>,
+        ReadExpect<'s, SurvivalStoreResource>,
+        ReadExpect<'s, WeaponStoreResource>,
+        ReadExpect<'s, DroneSpriteResource>,
+        ReadExpect<'s, LazyUpdate>,
+        Read<'s, Time>,
+    );
+
+    fn setup(&mut self, world: &mut World) {
+        let wave_break = world.read_resource::<SurvivalStoreResource>().properties.wave_break;
+
+        //the first break gives everyone time to get ready
+        self.wave_break = Cooldown::new(wave_break, wave_break);
+    }
+
+    fn run(
+        &mut self,
+        (
+            entities,
+            drones,
+            mut players,
+            mut healths,
+            transforms,
+            mut match_resource,
+            survival_store,
+            weapon_store,
+            drone_sprite_resource,
+            lazy_update,
+            time,
+        ): Self::SystemData,
+    ) {
+        if !match_resource.round_active() {
+            return;
+        }
+
+        let dt = time.delta_seconds();
+        let survival = &survival_store.properties;
+
+        // Waves
+        let drones_left = (&drones).join().count();
+
+        if self.pending_drones.is_empty() && drones_left == 0 {
+            if self.wave_in_progress {
+                self.wave_in_progress = false;
+                self.wave_break.timer_reset();
+                log::info!("wave {} cleared", self.wave);
+            }
+
+            self.wave_break.timer_update(&dt);
+
+            if self.wave_break.timer_active() {
+                self.pending_drones = survival_store.get_wave(self.wave).into_iter().collect();
+                self.spawn_cooldown = Cooldown::new(0.0, survival_store.get_spawn_interval(self.wave));
+                self.wave += 1;
+                self.wave_in_progress = true;
+                log::info!("wave {} incoming, {} drones", self.wave, self.pending_drones.len());
+            }
+        }
+
+        self.spawn_cooldown.timer_update(&dt);
+
+        if self.spawn_cooldown.timer_active() && !self.drone_spawns.is_empty() {
+            if let Some((kind, weapon_name)) = self.pending_drones.pop_front() {
+                let spawn = self.drone_spawns[self.drone_spawn_count % self.drone_spawns.len()];
+                self.drone_spawn_count += 1;
+
+                spawn_drone(
+                    &entities,
+                    kind,
+                    &survival_store.get_drone(&kind),
+                    weapon_name.map(|weapon_name| (weapon_name, weapon_store.get_properties(&weapon_name))),
+                    spawn,
+                    &drone_sprite_resource,
+                    &lazy_update,
+                );
+
+                self.spawn_cooldown.timer_reset();
+            }
+        }
+
+        // Revives
+        let rescuers: Vec<(f32, f32)> = (&players, &transforms)
+            .join()
+            .filter(|(player, _)| player.is_active())
+            .map(|(_, transform)| (transform.translation().x, transform.translation().y))
+            .collect();
+
+        for (player, health, transform) in (&mut players, &mut healths, &transforms).join() {
+            if self.revive_progress.len() <= player.id {
+                self.revive_progress.resize(player.id + 1, 0.0);
+            }
+
+            if player.get_state() != PlayerState::Downed {
+                self.revive_progress[player.id] = 0.0;
+                continue;
+            }
+
+            let x = transform.translation().x;
+            let y = transform.translation().y;
+
+            let rescued = rescuers.iter().any(|(rescuer_x, rescuer_y)| {
+                (rescuer_x - x).powi(2) + (rescuer_y - y).powi(2) <= survival.revive_range.powi(2)
+            });
+
+            if rescued {
+                self.revive_progress[player.id] += dt;
+            }
+            else {
+                self.revive_progress[player.id] = 0.0;
+            }
+
+            if self.revive_progress[player.id] >= survival.revive_time {
+                log::info!("p{} revived", player.id + 1);
+
+                self.revive_progress[player.id] = 0.0;
+                health.value = health.max * survival.revive_health;
+                player.clear_hits();
+                player.set_state(PlayerState::Active);
+            }
+        }
+
+        // Game over once nobody is left fighting
+        let fighting = (&players)
+            .join()
+            .any(|player| player.is_active() || player.get_state() == PlayerState::InRespawn);
+
+        if !fighting {
+            log::info!("all players down on wave {}, {} waves cleared", self.wave, self.wave.saturating_sub(1));
+            match_resource.end_round(None);
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use amethyst::{
+    core::{Time, Transform},
+    derive::SystemDesc,
+    ecs::{
+        Entities, Entity, Join, Read, ReadStorage, System, SystemData, World,
+        WriteStorage,
+    },
+};
+
+use ncollide2d::query::{self, Proximity};
+
+use crate::components::{
+    get_fixed_shape_pos, get_movable_shape_pos, ArenaElement, Hitbox, Movable, Teleporter};
+
+
+// Sends ships and shots entering a teleporter pad to its partner, keeping their speed.
+// A pad pair rests for a moment after each use, and what was just sent isn't sent back
+// until it has moved off the exit pad.
+#[derive(SystemDesc, Default)]
+pub struct TeleporterSystem {}
+
+impl<'s> System<'s> for TeleporterSystem {
+    type SystemData = (
+        Entities<'s>,
+        WriteStorage<'s, Teleporter>,
+        ReadStorage<'s, ArenaElement>,
+        ReadStorage<'s, Hitbox>,
+        WriteStorage<'s, Movable>,
+        WriteStorage<'s, Transform>,
+        Read<'s, Time>,
+    );
+
+    fn setup(&mut self, _world: &mut World) {
+    }
+
+    fn run(
+        &mut self,
+        (
+            entities,
+            mut teleporters,
+            arena_elements,
+            hitboxes,
+            mut movables,
+            mut transforms,
+            time,
+        ): Self::SystemData,
+    ) {
+        let dt = time.delta_seconds();
+
+        for teleporter in (&mut teleporters).join() {
+            teleporter.cooldown.timer_update(&dt);
+        }
+
+        // pad and entity pairs currently overlapping
+        let mut touching: Vec<(Entity, Entity)> = Vec::new();
+
+        for (pad_entity, _teleporter, pad_hitbox, pad_transform) in (
+            &entities,
+            &teleporters,
+            &hitboxes,
+            &transforms,
+        )
+            .join()
+        {
+            let (pad_pos, pad_shape) = get_fixed_shape_pos(pad_transform, pad_hitbox);
+
+            for (entity, _movable, hitbox, transform, _) in (
+                &entities,
+                &movables,
+                &hitboxes,
+                &transforms,
+                !&arena_elements,
+            )
+                .join()
+            {
+                let (pos, shape) = get_movable_shape_pos(transform, hitbox);
+
+                if query::proximity(&pad_pos, &*pad_shape, &pos, &shape, 0.0) == Proximity::Intersecting {
+                    touching.push((pad_entity, entity));
+                }
+            }
+        }
+
+        for (pad_entity, teleporter) in (&entities, &mut teleporters).join() {
+            teleporter.arrivals.retain(|id| {
+                touching.iter().any(|(pad, entity)| *pad == pad_entity && entity.id() == *id)
+            });
+        }
+
+        // entity, entry pad and exit pad
+        let mut sends: Vec<(Entity, Entity, Entity)> = Vec::new();
+
+        for (pad_entity, entity) in touching.iter() {
+            if sends.iter().any(|(sent, _, _)| sent == entity) {
+                continue;
+            }
+
+            // a send rests both pads of the pair, so only one send per pair each frame
+            if sends.iter().any(|(_, entry, exit)| entry == pad_entity || exit == pad_entity) {
+                continue;
+            }
+
+            let pad = match teleporters.get(*pad_entity) {
+                Some(pad) => pad,
+                None => continue,
+            };
+            if !pad.cooldown.timer_active() || pad.arrivals.contains(&entity.id()) {
+                continue;
+            }
+
+            let exit = (&entities, &teleporters)
+                .join()
+                .find(|(exit_entity, exit)| exit_entity != pad_entity && exit.pair_id == pad.pair_id)
+                .map(|(exit_entity, _)| exit_entity);
+
+            if let Some(exit_entity) = exit {
+                sends.push((*entity, *pad_entity, exit_entity));
+            }
+        }
+
+        for (entity, pad_entity, exit_entity) in sends {
+            let exit_position = match transforms.get(exit_entity) {
+                Some(exit_transform) => (exit_transform.translation().x, exit_transform.translation().y),
+                None => continue,
+            };
+
+            let turn = match (teleporters.get(pad_entity), teleporters.get(exit_entity), movables.get_mut(entity)) {
+                (Some(pad), Some(exit), Some(movable)) => {
+                    let (turn, dx, dy) = pad.turn_to(exit, movable.dx, movable.dy);
+                    movable.dx = dx;
+                    movable.dy = dy;
+                    turn
+                },
+                _ => continue,
+            };
+
+            if let Some(transform) = transforms.get_mut(entity) {
+                transform.set_translation_x(exit_position.0);
+                transform.set_translation_y(exit_position.1);
+                transform.rotate_2d(turn);
+            }
+
+            if let Some(pad) = teleporters.get_mut(pad_entity) {
+                pad.cooldown.timer_reset();
+            }
+            if let Some(exit) = teleporters.get_mut(exit_entity) {
+                exit.cooldown.timer_reset();
+                exit.arrivals.push(entity.id());
+            }
+        }
+    }
+}